@@ -4,31 +4,41 @@ pub fn filter_attributes(attrs: &[Attribute], allowed_idents: &[&str]) -> syn::R
   let mut metas = Vec::new();
 
   for attr in attrs {
-    let attr_ident = if let Some(ident) = attr.path().get_ident() {
-      ident.to_string()
-    } else {
-      continue;
-    };
-
-    if !allowed_idents.contains(&attr_ident.as_str()) {
+    if !attr_matches(attr, allowed_idents) {
       continue;
     }
 
-    let parser = |input: ParseStream| -> syn::Result<()> {
-      while !input.is_empty() {
-        let meta: Meta = input.parse()?;
-        metas.push(meta);
+    metas.extend(parse_attr_metas(attr)?);
+  }
+
+  Ok(metas)
+}
+
+/// Parses the comma-separated list of metas inside of an attribute like `#[my_attr(a, b = 1, c(d))]`.
+pub fn parse_attr_metas(attr: &Attribute) -> syn::Result<Vec<Meta>> {
+  attr.parse_args_with(parse_meta_list)
+}
+
+pub(crate) fn parse_meta_list(input: ParseStream) -> syn::Result<Vec<Meta>> {
+  let mut metas = Vec::new();
 
-        if input.is_empty() {
-          break;
-        }
-        let _: Token![,] = input.parse()?;
-      }
-      Ok(())
-    };
+  while !input.is_empty() {
+    let meta: Meta = input.parse()?;
+    metas.push(meta);
 
-    attr.parse_args_with(parser)?;
+    if input.is_empty() {
+      break;
+    }
+    let _: Token![,] = input.parse()?;
   }
 
   Ok(metas)
 }
+
+pub(crate) fn attr_matches(attr: &Attribute, idents: &[&str]) -> bool {
+  if let Some(ident) = attr.path().get_ident() {
+    idents.contains(&ident.to_string().as_str())
+  } else {
+    false
+  }
+}
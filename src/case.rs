@@ -0,0 +1,176 @@
+use syn::ext::IdentExt;
+
+use crate::*;
+
+/// A case conversion, as used in `rename_all = "..."` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenameRule {
+  /// `lowercase`
+  LowerCase,
+  /// `UPPERCASE`
+  UpperCase,
+  /// `PascalCase`
+  PascalCase,
+  /// `camelCase`
+  CamelCase,
+  /// `snake_case`
+  SnakeCase,
+  /// `SCREAMING_SNAKE_CASE`
+  ScreamingSnakeCase,
+  /// `kebab-case`
+  KebabCase,
+  /// `SCREAMING-KEBAB-CASE`
+  ScreamingKebabCase,
+}
+
+impl RenameRule {
+  pub const ALL: [(&'static str, Self); 8] = [
+    ("lowercase", Self::LowerCase),
+    ("UPPERCASE", Self::UpperCase),
+    ("PascalCase", Self::PascalCase),
+    ("camelCase", Self::CamelCase),
+    ("snake_case", Self::SnakeCase),
+    ("SCREAMING_SNAKE_CASE", Self::ScreamingSnakeCase),
+    ("kebab-case", Self::KebabCase),
+    ("SCREAMING-KEBAB-CASE", Self::ScreamingKebabCase),
+  ];
+
+  pub fn as_str(&self) -> &'static str {
+    Self::ALL
+      .iter()
+      .find(|(_, rule)| rule == self)
+      .map(|(name, _)| *name)
+      .unwrap()
+  }
+
+  /// Applies the conversion to a string.
+  ///
+  /// Words are split at `_`, `-` and whitespace, and at case boundaries (so `HTTPServer` becomes `http_server`).
+  /// `lowercase` and `UPPERCASE` only change the case of the input, without touching the separators.
+  pub fn apply(&self, input: &str) -> String {
+    match self {
+      Self::LowerCase => return input.to_lowercase(),
+      Self::UpperCase => return input.to_uppercase(),
+      _ => {}
+    };
+
+    let words = split_words(input);
+
+    match self {
+      Self::PascalCase => words.iter().map(|w| capitalize(w)).collect(),
+      Self::CamelCase => words
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+          if i == 0 {
+            w.to_lowercase()
+          } else {
+            capitalize(w)
+          }
+        })
+        .collect(),
+      Self::SnakeCase => join_words(&words, "_", false),
+      Self::ScreamingSnakeCase => join_words(&words, "_", true),
+      Self::KebabCase => join_words(&words, "-", false),
+      Self::ScreamingKebabCase => join_words(&words, "-", true),
+      Self::LowerCase | Self::UpperCase => unreachable!(),
+    }
+  }
+
+  /// Applies the conversion to an ident, stripping the `r#` prefix from raw identifiers.
+  pub fn apply_to_ident(&self, ident: &Ident) -> String {
+    self.apply(&ident.unraw().to_string())
+  }
+}
+
+impl Display for RenameRule {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for RenameRule {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .iter()
+      .find(|(name, _)| *name == s)
+      .map(|(_, rule)| *rule)
+      .ok_or_else(|| {
+        let names: Vec<String> = Self::ALL
+          .iter()
+          .map(|(name, _)| format!("`{name}`"))
+          .collect();
+
+        format!(
+          "Unknown rename rule `{s}`, expected one of: {}",
+          names.join(", ")
+        )
+      })
+  }
+}
+
+impl Parse for RenameRule {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let lit: LitStr = input.parse()?;
+
+    lit.value().parse().map_err(|e: String| error!(lit, "{e}"))
+  }
+}
+
+fn split_words(input: &str) -> Vec<String> {
+  let mut words = Vec::new();
+
+  for chunk in input.split(|c: char| c == '_' || c == '-' || c.is_whitespace()) {
+    let chars: Vec<char> = chunk.chars().collect();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+      if c.is_uppercase() && !current.is_empty() {
+        let prev = chars[i - 1];
+        let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+        // Splits `fooBar` as well as the last capital in an acronym, like `HTTPServer`
+        if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+          words.push(std::mem::take(&mut current));
+        }
+      }
+
+      current.push(c);
+    }
+
+    if !current.is_empty() {
+      words.push(current);
+    }
+  }
+
+  words
+}
+
+fn capitalize(word: &str) -> String {
+  let mut chars = word.chars();
+
+  match chars.next() {
+    Some(first) => first
+      .to_uppercase()
+      .chain(chars.flat_map(char::to_lowercase))
+      .collect(),
+    None => String::new(),
+  }
+}
+
+fn join_words(words: &[String], separator: &str, uppercase: bool) -> String {
+  let words: Vec<String> = words
+    .iter()
+    .map(|w| {
+      if uppercase {
+        w.to_uppercase()
+      } else {
+        w.to_lowercase()
+      }
+    })
+    .collect();
+
+  words.join(separator)
+}
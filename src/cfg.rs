@@ -0,0 +1,236 @@
+use syn::{LitBool, MetaList};
+
+use crate::*;
+
+/// A parsed `cfg` predicate, such as the first argument of `#[cfg_attr(all(feature = "a", not(test)), ...)]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgPredicate {
+  All(Vec<CfgPredicate>),
+  Any(Vec<CfgPredicate>),
+  Not(Box<CfgPredicate>),
+  /// A bare option, like `test` or `unix`.
+  Flag(Ident),
+  /// A key-value option, like `feature = "serde"`.
+  KeyValue(Ident, LitStr),
+  /// `true` or `false`.
+  Bool(LitBool),
+}
+
+impl Parse for CfgPredicate {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    if input.peek(LitBool) {
+      return Ok(Self::Bool(input.parse()?));
+    }
+
+    let ident: Ident = input.parse()?;
+
+    if input.peek(token::Paren) {
+      let content;
+      syn::parenthesized!(content in input);
+
+      let mut predicates: Vec<Self> = Punctuated::<Self, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .collect();
+
+      let output = match ident.to_string().as_str() {
+        "all" => Self::All(predicates),
+        "any" => Self::Any(predicates),
+        "not" => {
+          if predicates.len() != 1 {
+            bail!(ident, "`not` expects exactly one predicate");
+          }
+
+          Self::Not(Box::new(predicates.remove(0)))
+        }
+        _ => bail!(
+          ident,
+          "Unknown cfg predicate `{ident}`, expected `all`, `any` or `not`"
+        ),
+      };
+
+      Ok(output)
+    } else if input.peek(Token![=]) {
+      let _: Token![=] = input.parse()?;
+
+      Ok(Self::KeyValue(ident, input.parse()?))
+    } else {
+      Ok(Self::Flag(ident))
+    }
+  }
+}
+
+impl ToTokens for CfgPredicate {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let output = match self {
+      Self::All(predicates) => quote! { all(#(#predicates),*) },
+      Self::Any(predicates) => quote! { any(#(#predicates),*) },
+      Self::Not(predicate) => quote! { not(#predicate) },
+      Self::Flag(ident) => quote! { #ident },
+      Self::KeyValue(key, value) => quote! { #key = #value },
+      Self::Bool(value) => quote! { #value },
+    };
+
+    tokens.extend(output);
+  }
+}
+
+impl CfgPredicate {
+  /// Returns a `#[cfg(...)]` attribute with this predicate, to emit generated code under the same conditions.
+  pub fn to_cfg_attr(&self) -> Attribute {
+    parse_quote!(#[cfg(#self)])
+  }
+
+  /// Evaluates the predicate, using the callback to check whether a single option is active.
+  ///
+  /// The callback receives the name of the option and, for key-value options, the value.
+  pub fn evaluate<F>(&self, is_active: &mut F) -> bool
+  where
+    F: FnMut(&str, Option<&str>) -> bool,
+  {
+    match self {
+      Self::All(predicates) => predicates.iter().all(|p| p.evaluate(is_active)),
+      Self::Any(predicates) => predicates.iter().any(|p| p.evaluate(is_active)),
+      Self::Not(predicate) => !predicate.evaluate(is_active),
+      Self::Flag(ident) => is_active(&ident.to_string(), None),
+      Self::KeyValue(key, value) => is_active(&key.to_string(), Some(&value.value())),
+      Self::Bool(value) => value.value,
+    }
+  }
+
+  /// Evaluates the predicate against a list of active features.
+  ///
+  /// Anything other than `feature = "..."` is considered inactive.
+  pub fn evaluate_features(&self, features: &[&str]) -> bool {
+    self.evaluate(&mut |key, value| {
+      key == "feature" && value.is_some_and(|value| features.contains(&value))
+    })
+  }
+
+  fn and(outer: Option<&Self>, inner: Self) -> Self {
+    match outer {
+      Some(outer) => Self::All(vec![outer.clone(), inner]),
+      None => inner,
+    }
+  }
+}
+
+/// A meta that was found with [`filter_attributes_with_cfg`], along with the predicate
+/// of the `cfg_attr` that it was wrapped in (if there was one).
+#[derive(Debug, Clone)]
+pub struct CfgMeta {
+  pub meta: Meta,
+  pub cfg: Option<CfgPredicate>,
+}
+
+impl CfgMeta {
+  /// Returns the `#[cfg(...)]` attribute that should be placed on the code generated from this meta.
+  pub fn cfg_attr(&self) -> Option<Attribute> {
+    self.cfg.as_ref().map(|cfg| cfg.to_cfg_attr())
+  }
+}
+
+/// Like [`filter_attributes`], but it also descends into `#[cfg_attr(predicate, ...)]` attributes,
+/// returning the metas inside of them together with their predicate.
+///
+/// Nested `cfg_attr`s have their predicates combined with `all(...)`, and the ones that don't contain
+/// any of the allowed attributes are skipped without parsing their predicate.
+pub fn filter_attributes_with_cfg(
+  attrs: &[Attribute],
+  allowed_idents: &[&str],
+) -> syn::Result<Vec<CfgMeta>> {
+  let mut metas = Vec::new();
+
+  for attr in attrs {
+    if attr_matches(attr, allowed_idents) {
+      metas.extend(
+        parse_attr_metas(attr)?
+          .into_iter()
+          .map(|meta| CfgMeta { meta, cfg: None }),
+      );
+    } else if attr.path().is_ident("cfg_attr") {
+      let list = attr.meta.require_list()?;
+
+      collect_cfg_attr(list, None, allowed_idents, &mut metas)?;
+    }
+  }
+
+  Ok(metas)
+}
+
+fn collect_cfg_attr(
+  list: &MetaList,
+  outer: Option<&CfgPredicate>,
+  allowed_idents: &[&str],
+  metas: &mut Vec<CfgMeta>,
+) -> syn::Result<()> {
+  let (predicate, inner_attrs) = split_cfg_attr(list)?;
+
+  if !inner_attrs
+    .iter()
+    .any(|inner| contains_allowed(inner, allowed_idents))
+  {
+    return Ok(());
+  }
+
+  let predicate: CfgPredicate = syn::parse2(predicate)?;
+  let predicate = CfgPredicate::and(outer, predicate);
+
+  for inner in inner_attrs {
+    let Meta::List(inner_list) = &inner else {
+      continue;
+    };
+
+    if inner_list.path.is_ident("cfg_attr") {
+      collect_cfg_attr(inner_list, Some(&predicate), allowed_idents, metas)?;
+    } else if let Some(ident) = inner_list.path.get_ident()
+      && allowed_idents.contains(&ident.to_string().as_str())
+    {
+      metas.extend(
+        inner_list
+          .parse_args_with(parse_meta_list)?
+          .into_iter()
+          .map(|meta| CfgMeta {
+            meta,
+            cfg: Some(predicate.clone()),
+          }),
+      );
+    }
+  }
+
+  Ok(())
+}
+
+// Splits a `cfg_attr` into the tokens of its predicate and its inner attributes
+fn split_cfg_attr(list: &MetaList) -> syn::Result<(TokenStream2, Punctuated<Meta, Token![,]>)> {
+  list.parse_args_with(|input: ParseStream| {
+    let mut predicate = TokenStream2::new();
+
+    while !input.is_empty() && !input.peek(Token![,]) {
+      predicate.extend([input.parse::<proc_macro2::TokenTree>()?]);
+    }
+
+    let _: Token![,] = input.parse()?;
+    let inner_attrs = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+
+    Ok((predicate, inner_attrs))
+  })
+}
+
+fn contains_allowed(meta: &Meta, allowed_idents: &[&str]) -> bool {
+  let Meta::List(list) = meta else {
+    return false;
+  };
+
+  if list.path.is_ident("cfg_attr") {
+    split_cfg_attr(list).is_ok_and(|(_, inner_attrs)| {
+      inner_attrs
+        .iter()
+        .any(|inner| contains_allowed(inner, allowed_idents))
+    })
+  } else {
+    list
+      .path
+      .get_ident()
+      .is_some_and(|ident| allowed_idents.contains(&ident.to_string().as_str()))
+  }
+}
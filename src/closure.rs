@@ -0,0 +1,130 @@
+use syn::{GenericParam, ItemFn, Pat, ReturnType};
+
+use crate::*;
+
+#[derive(Debug, Clone)]
+pub struct ClosureParam {
+  /// The attributes of a typed parameter, like `#[allow(unused)]` in `|#[allow(unused)] x: i32|`.
+  pub attrs: Vec<Attribute>,
+  pub pat: Pat,
+  /// The type annotation of the parameter, if there is one.
+  pub ty: Option<Type>,
+  /// The parsed type annotation, if there is one and it's supported by [`TypeInfo`].
+  pub type_info: Option<TypeInfo>,
+}
+
+/// The signature and body of a closure, as extracted by [`ClosureInfo::from_closure`].
+#[derive(Debug, Clone)]
+pub struct ClosureInfo {
+  /// The lifetimes bound with `for<'a>`, which become the generics of the lowered function.
+  pub lifetimes: Vec<GenericParam>,
+  pub params: Vec<ClosureParam>,
+  /// The return type annotation of the closure, if there is one.
+  pub output: Option<Type>,
+  /// The parsed return type, if there is one and it's supported by [`TypeInfo`].
+  pub output_info: Option<TypeInfo>,
+  pub is_move: bool,
+  pub is_async: bool,
+  pub body: Expr,
+}
+
+impl ClosureInfo {
+  pub fn from_closure(closure: &ExprClosure) -> Self {
+    let params = closure
+      .inputs
+      .iter()
+      .map(|input| {
+        let (attrs, pat, ty) = if let Pat::Type(pat_type) = input {
+          (
+            pat_type.attrs.clone(),
+            pat_type.pat.as_ref().clone(),
+            Some(pat_type.ty.as_ref().clone()),
+          )
+        } else {
+          (Vec::new(), input.clone(), None)
+        };
+
+        // Types that TypeInfo can't parse (like `&dyn Display`) are still valid in the closure
+        let type_info = ty.as_ref().and_then(|ty| TypeInfo::from_type(ty).ok());
+
+        ClosureParam {
+          attrs,
+          pat,
+          ty,
+          type_info,
+        }
+      })
+      .collect();
+
+    let output = match &closure.output {
+      ReturnType::Default => None,
+      ReturnType::Type(_, ty) => Some(ty.as_ref().clone()),
+    };
+
+    let output_info = output.as_ref().and_then(|ty| TypeInfo::from_type(ty).ok());
+
+    Self {
+      lifetimes: closure
+        .lifetimes
+        .as_ref()
+        .map(|bound| bound.lifetimes.iter().cloned().collect())
+        .unwrap_or_default(),
+      params,
+      output,
+      output_info,
+      is_move: closure.capture.is_some(),
+      is_async: closure.asyncness.is_some(),
+      body: closure.body.as_ref().clone(),
+    }
+  }
+
+  pub fn arity(&self) -> usize {
+    self.params.len()
+  }
+
+  /// Lowers the closure into a function item with the given name.
+  ///
+  /// Lifetimes bound with `for<...>` are declared as the generics of the function.
+  ///
+  /// Parameters without a type annotation take the type at the same position in `param_types`,
+  /// and the return type falls back to `output` if the closure doesn't have one (or to `()` if neither is present).
+  pub fn to_fn(
+    &self,
+    name: &Ident,
+    param_types: &[Type],
+    output: Option<&Type>,
+  ) -> syn::Result<ItemFn> {
+    let inputs = self
+      .params
+      .iter()
+      .enumerate()
+      .map(|(i, param)| {
+        let ty = param
+          .ty
+          .as_ref()
+          .or_else(|| param_types.get(i))
+          .ok_or_else(|| error!(param.pat, "Missing a type for this closure parameter"))?;
+        let attrs = &param.attrs;
+        let pat = &param.pat;
+
+        Ok(quote! { #(#attrs)* #pat: #ty })
+      })
+      .collect::<syn::Result<Vec<TokenStream2>>>()?;
+
+    let output = self.output.as_ref().or(output).map(|ty| quote! { -> #ty });
+
+    let asyncness = self.is_async.then(|| quote! { async });
+
+    let body = match &self.body {
+      Expr::Block(block) if block.label.is_none() => block.block.to_token_stream(),
+      body => quote! { { #body } },
+    };
+
+    let lifetimes = &self.lifetimes;
+    let generics = (!lifetimes.is_empty()).then(|| quote! { <#(#lifetimes),*> });
+
+    Ok(parse_quote! {
+      #asyncness fn #name #generics(#(#inputs),*) #output #body
+    })
+  }
+}
@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+
+use syn::{BinOp, UnOp};
+
+use crate::*;
+
+/// An evaluator for constant integer expressions, such as `1 << 10`, `4 * 1024` or `-(2i32.pow(3))`.
+///
+/// It supports arithmetic, bitwise and shift operators, negation, parentheses, casts between integer types,
+/// the `MIN`, `MAX` and `BITS` constants of the integer types, the `pow`, `abs`, `min` and `max` methods,
+/// and any named constant supplied by the caller.
+///
+/// Values are computed as `i128` (so `u128` values above `i128::MAX` are not supported). When a value has a type
+/// (from a suffix, a cast or a typed constant), every operation on it is checked for overflow against that type.
+#[derive(Debug, Clone, Default)]
+pub struct ConstEvaluator {
+  constants: HashMap<String, i128>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntKind {
+  Int(Int),
+  Uint(Uint),
+}
+
+impl IntKind {
+  fn from_name(name: &str) -> Option<Self> {
+    let kind = match name {
+      "isize" => Self::Int(Int::ISize),
+      "i8" => Self::Int(Int::I8),
+      "i16" => Self::Int(Int::I16),
+      "i32" => Self::Int(Int::I32),
+      "i64" => Self::Int(Int::I64),
+      "i128" => Self::Int(Int::I128),
+      "usize" => Self::Uint(Uint::USize),
+      "u8" => Self::Uint(Uint::U8),
+      "u16" => Self::Uint(Uint::U16),
+      "u32" => Self::Uint(Uint::U32),
+      "u64" => Self::Uint(Uint::U64),
+      "u128" => Self::Uint(Uint::U128),
+      _ => return None,
+    };
+
+    Some(kind)
+  }
+
+  fn bits(self) -> u32 {
+    match self {
+      Self::Int(Int::I8) | Self::Uint(Uint::U8) => 8,
+      Self::Int(Int::I16) | Self::Uint(Uint::U16) => 16,
+      Self::Int(Int::I32) | Self::Uint(Uint::U32) => 32,
+      Self::Int(Int::I64 | Int::ISize) | Self::Uint(Uint::U64 | Uint::USize) => 64,
+      Self::Int(Int::I128) | Self::Uint(Uint::U128) => 128,
+    }
+  }
+
+  fn is_signed(self) -> bool {
+    matches!(self, Self::Int(_))
+  }
+
+  fn min(self) -> i128 {
+    if self.is_signed() {
+      i128::MIN >> (128 - self.bits())
+    } else {
+      0
+    }
+  }
+
+  fn max(self) -> i128 {
+    match self {
+      Self::Int(_) => i128::MAX >> (128 - self.bits()),
+      Self::Uint(Uint::U128) => i128::MAX,
+      Self::Uint(_) => (1i128 << self.bits()) - 1,
+    }
+  }
+
+  fn name(self) -> String {
+    match self {
+      Self::Int(int) => int.to_token_stream().to_string(),
+      Self::Uint(uint) => uint.to_token_stream().to_string(),
+    }
+  }
+
+  // Same semantics as an `as` cast
+  fn wrap(self, value: i128) -> Option<i128> {
+    if self.bits() == 128 {
+      return (self.is_signed() || value >= 0).then_some(value);
+    }
+
+    let modulus = 1i128 << self.bits();
+    let wrapped = value.rem_euclid(modulus);
+
+    if self.is_signed() && wrapped > self.max() {
+      Some(wrapped - modulus)
+    } else {
+      Some(wrapped)
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Value {
+  value: i128,
+  kind: Option<IntKind>,
+}
+
+impl ConstEvaluator {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a named constant, which can be referred to with its path (like `MAX_LEN` or `limits::MAX_LEN`).
+  pub fn with_constant(mut self, name: impl Into<String>, value: i128) -> Self {
+    self.add_constant(name, value);
+    self
+  }
+
+  pub fn add_constant(&mut self, name: impl Into<String>, value: i128) {
+    self.constants.insert(name.into(), value);
+  }
+
+  pub fn eval(&self, expr: &Expr) -> syn::Result<i128> {
+    Ok(self.eval_value(expr)?.value)
+  }
+
+  /// Evaluates the expression and converts it to the target type, returning an error if it doesn't fit.
+  pub fn eval_as<N>(&self, expr: &Expr) -> syn::Result<N>
+  where
+    N: TryFrom<i128>,
+  {
+    let value = self.eval(expr)?;
+
+    N::try_from(value).map_err(|_| {
+      error!(
+        expr,
+        "Value `{value}` is out of range for `{}`",
+        std::any::type_name::<N>()
+      )
+    })
+  }
+
+  fn eval_value(&self, expr: &Expr) -> syn::Result<Value> {
+    match expr {
+      Expr::Paren(paren) => self.eval_value(&paren.expr),
+      Expr::Group(group) => self.eval_value(&group.expr),
+      Expr::Lit(_) => {
+        let (value, kind) = eval_literal(expr)?;
+
+        check_range(expr, value, kind)
+      }
+      // The literal is only checked after the negation, so that `-128i8` is accepted like in Rust
+      Expr::Unary(unary)
+        if let UnOp::Neg(_) = unary.op
+          && let Expr::Lit(_) = unary.expr.as_ref() =>
+      {
+        let (value, kind) = eval_literal(&unary.expr)?;
+
+        if kind.is_some_and(|k| !k.is_signed()) {
+          bail!(expr, "Cannot negate an unsigned integer");
+        }
+
+        check_range(expr, -value, kind)
+      }
+      Expr::Unary(unary) => {
+        let operand = self.eval_value(&unary.expr)?;
+
+        let value = match unary.op {
+          UnOp::Neg(_) => {
+            if operand.kind.is_some_and(|k| !k.is_signed()) {
+              bail!(expr, "Cannot negate an unsigned integer");
+            }
+
+            operand
+              .value
+              .checked_neg()
+              .ok_or_else(|| error!(expr, "Integer overflow"))?
+          }
+          UnOp::Not(_) => match operand.kind {
+            Some(kind) if !kind.is_signed() => kind.max() ^ operand.value,
+            _ => !operand.value,
+          },
+          _ => bail!(expr, "Unsupported unary operator"),
+        };
+
+        check_range(expr, value, operand.kind)
+      }
+      Expr::Binary(binary) => {
+        let left = self.eval_value(&binary.left)?;
+        let right = self.eval_value(&binary.right)?;
+
+        let is_shift = matches!(binary.op, BinOp::Shl(_) | BinOp::Shr(_));
+
+        let kind = if is_shift {
+          left.kind
+        } else {
+          match (left.kind, right.kind) {
+            (Some(l), Some(r)) if l != r => bail!(
+              expr,
+              "Mismatched integer types `{}` and `{}`",
+              l.name(),
+              r.name()
+            ),
+            (l, r) => l.or(r),
+          }
+        };
+
+        let (l, r) = (left.value, right.value);
+
+        let value = match binary.op {
+          BinOp::Add(_) => l.checked_add(r),
+          BinOp::Sub(_) => l.checked_sub(r),
+          BinOp::Mul(_) => l.checked_mul(r),
+          BinOp::Div(_) | BinOp::Rem(_) if r == 0 => bail!(expr, "Division by zero"),
+          BinOp::Div(_) => l.checked_div(r),
+          BinOp::Rem(_) => l.checked_rem(r),
+          BinOp::BitAnd(_) => Some(l & r),
+          BinOp::BitOr(_) => Some(l | r),
+          BinOp::BitXor(_) => Some(l ^ r),
+          BinOp::Shl(_) | BinOp::Shr(_) => {
+            let bits = kind.map_or(128, |k| k.bits());
+
+            let amount = u32::try_from(r)
+              .ok()
+              .filter(|amount| *amount < bits)
+              .ok_or_else(|| error!(binary.right, "Shift amount out of range"))?;
+
+            match (binary.op, kind) {
+              (BinOp::Shr(_), _) => Some(l >> amount),
+              // Typed shifts discard the bits that overflow, like in Rust
+              (_, Some(kind)) => kind.wrap(l.wrapping_shl(amount)),
+              // Untyped shifts must fit in the `i128` range
+              (_, None) => {
+                let shifted = l.wrapping_shl(amount);
+
+                (shifted >> amount == l).then_some(shifted)
+              }
+            }
+          }
+          _ => bail!(expr, "Unsupported binary operator"),
+        };
+
+        let value = value.ok_or_else(|| error!(expr, "Integer overflow"))?;
+
+        check_range(expr, value, kind)
+      }
+      Expr::Cast(cast) => {
+        let operand = self.eval_value(&cast.expr)?;
+
+        let kind = if let Type::Path(type_path) = cast.ty.as_ref()
+          && let Some(ident) = type_path.path.get_ident()
+        {
+          IntKind::from_name(&ident.to_string())
+        } else {
+          None
+        }
+        .ok_or_else(|| error!(cast.ty, "Expected an integer type"))?;
+
+        let value = kind
+          .wrap(operand.value)
+          .ok_or_else(|| error!(expr, "Value is out of range"))?;
+
+        Ok(Value {
+          value,
+          kind: Some(kind),
+        })
+      }
+      Expr::Path(expr_path) => {
+        let path = &expr_path.path;
+        let name = path.to_token_stream().to_string().replace(' ', "");
+
+        if let Some(value) = self.constants.get(&name) {
+          return Ok(Value {
+            value: *value,
+            kind: None,
+          });
+        }
+
+        if path.segments.len() == 2
+          && let Some(kind) = IntKind::from_name(&path.segments[0].ident.to_string())
+        {
+          let constant = path.segments[1].ident.to_string();
+
+          let value = match constant.as_str() {
+            "MIN" => Value {
+              value: kind.min(),
+              kind: Some(kind),
+            },
+            "MAX" => Value {
+              value: kind.max(),
+              kind: Some(kind),
+            },
+            "BITS" => Value {
+              value: kind.bits().into(),
+              kind: Some(IntKind::Uint(Uint::U32)),
+            },
+            _ => bail!(path, "Unknown constant `{name}`"),
+          };
+
+          return Ok(value);
+        }
+
+        bail!(path, "Unknown constant `{name}`")
+      }
+      Expr::MethodCall(call) => {
+        let receiver = self.eval_value(&call.receiver)?;
+        let args = call
+          .args
+          .iter()
+          .map(|arg| self.eval_value(arg))
+          .collect::<syn::Result<Vec<Value>>>()?;
+
+        let method = call.method.to_string();
+
+        let value = match (method.as_str(), args.as_slice()) {
+          ("pow", [exp]) => {
+            let exp =
+              u32::try_from(exp.value).map_err(|_| error!(call.args, "Invalid exponent"))?;
+
+            receiver.value.checked_pow(exp)
+          }
+          ("abs", []) => receiver.value.checked_abs(),
+          ("min", [other]) => Some(receiver.value.min(other.value)),
+          ("max", [other]) => Some(receiver.value.max(other.value)),
+          _ => bail!(
+            call.method,
+            "Unsupported method `{method}`, expected one of `pow`, `abs`, `min` or `max`"
+          ),
+        };
+
+        let value = value.ok_or_else(|| error!(expr, "Integer overflow"))?;
+
+        check_range(expr, value, receiver.kind)
+      }
+      _ => bail!(expr, "Unsupported expression in constant integer context"),
+    }
+  }
+}
+
+fn eval_literal(expr: &Expr) -> syn::Result<(i128, Option<IntKind>)> {
+  let Expr::Lit(expr_lit) = expr else {
+    bail!(expr, "Expected an integer literal");
+  };
+
+  let Lit::Int(lit) = &expr_lit.lit else {
+    bail!(expr, "Expected an integer literal");
+  };
+
+  let value: i128 = lit.base10_parse()?;
+
+  let kind = match lit.suffix() {
+    "" => None,
+    suffix => Some(
+      IntKind::from_name(suffix).ok_or_else(|| error!(lit, "Unknown integer suffix `{suffix}`"))?,
+    ),
+  };
+
+  Ok((value, kind))
+}
+
+fn check_range(expr: &Expr, value: i128, kind: Option<IntKind>) -> syn::Result<Value> {
+  if let Some(kind) = kind
+    && (value < kind.min() || value > kind.max())
+  {
+    bail!(
+      expr,
+      "Value `{value}` is out of range for `{}`",
+      kind.name()
+    );
+  }
+
+  Ok(Value { value, kind })
+}
+
+impl Array {
+  /// Evaluates the length of the array as a constant expression.
+  pub fn eval_len(&self, evaluator: &ConstEvaluator) -> syn::Result<usize> {
+    evaluator.eval_as(&self.len)
+  }
+}
@@ -1,5 +1,7 @@
 use crate::*;
 
+/// Drives a macro expansion, so that when it fails, a dummy (such as a stub trait impl or the untouched input item)
+/// is emitted next to the errors. This keeps the downstream code type-checking, avoiding cascades of unrelated errors.
 #[derive(Default, Clone, Debug)]
 pub struct ControlFlow {
   pub dummy: Option<TokenStream2>,
@@ -15,10 +17,41 @@ impl ControlFlow {
       dummy: Some(dummy.to_token_stream()),
     }
   }
+
+  /// Sets the dummy to emit in case of errors, replacing the previous one.
+  pub fn set_dummy(&mut self, dummy: impl ToTokens) {
+    self.dummy = Some(dummy.into_token_stream());
+  }
+
+  /// Turns the result of an expansion into tokens. If it's an error, the dummy is emitted after the errors.
+  pub fn expand<T: ToTokens>(&self, result: syn::Result<T>) -> TokenStream2 {
+    match result {
+      Ok(output) => output.into_token_stream(),
+      Err(error) => {
+        let error = error.into_compile_error();
+        let dummy = &self.dummy;
+
+        quote! { #error #dummy }
+      }
+    }
+  }
+
+  /// Runs the expansion, which can set or replace the dummy as it goes (for example once the input has been parsed),
+  /// and turns its result into tokens with [`ControlFlow::expand`].
+  pub fn run<T, F>(mut self, expansion: F) -> TokenStream2
+  where
+    T: ToTokens,
+    F: FnOnce(&mut Self) -> syn::Result<T>,
+  {
+    let result = expansion(&mut self);
+
+    self.expand(result)
+  }
 }
 
 pub trait MacroError {
   fn append_unimplemented(self) -> TokenStream2;
+  fn append_dummy(self, control_flow: &ControlFlow) -> TokenStream2;
 }
 
 impl MacroError for syn::Error {
@@ -27,10 +60,17 @@ impl MacroError for syn::Error {
 
     quote! { #error; unimplemented!() }
   }
+
+  fn append_dummy(self, control_flow: &ControlFlow) -> TokenStream2 {
+    control_flow.expand::<TokenStream2>(Err(self))
+  }
 }
 
 pub trait MacroResult: Sized {
+  /// Emits the errors followed by `unimplemented!()`. Only usable in expression position.
   fn unwrap_or_unimplemented(self) -> TokenStream2;
+  /// Emits the errors followed by the dummy of the [`ControlFlow`], if there is one.
+  fn unwrap_or_dummy(self, control_flow: &ControlFlow) -> TokenStream2;
 }
 
 impl<T: ToTokens> MacroResult for syn::Result<T> {
@@ -44,4 +84,8 @@ impl<T: ToTokens> MacroResult for syn::Result<T> {
       |v| v.into_token_stream(),
     )
   }
+
+  fn unwrap_or_dummy(self, control_flow: &ControlFlow) -> TokenStream2 {
+    control_flow.expand(self)
+  }
 }
@@ -0,0 +1,123 @@
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticLevel {
+  Note,
+  Help,
+}
+
+impl Display for DiagnosticLevel {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Note => f.write_str("note"),
+      Self::Help => f.write_str("help"),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiagnosticChild {
+  pub level: DiagnosticLevel,
+  /// If this is `None`, the message is attached to the primary error.
+  pub span: Option<Span>,
+  pub message: String,
+}
+
+/// An error with additional notes and help messages, optionally pointing at other spans.
+///
+/// Since `syn::Error` only supports a single message per span, this is lowered to
+/// a combination of errors, where each labelled child becomes its own error at its own span.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+  span: Span,
+  tokens: Option<TokenStream2>,
+  pub message: String,
+  pub children: Vec<DiagnosticChild>,
+}
+
+impl Diagnostic {
+  pub fn new(span: Span, message: impl Into<String>) -> Self {
+    Self {
+      span,
+      tokens: None,
+      message: message.into(),
+      children: Vec::new(),
+    }
+  }
+
+  /// Creates a diagnostic that spans the given tokens, like [`syn::Error::new_spanned`].
+  pub fn spanned(tokens: impl ToTokens, message: impl Into<String>) -> Self {
+    let tokens = tokens.into_token_stream();
+
+    Self {
+      span: tokens.span(),
+      tokens: Some(tokens),
+      message: message.into(),
+      children: Vec::new(),
+    }
+  }
+
+  pub fn child(
+    mut self,
+    level: DiagnosticLevel,
+    span: Option<Span>,
+    message: impl Into<String>,
+  ) -> Self {
+    self.children.push(DiagnosticChild {
+      level,
+      span,
+      message: message.into(),
+    });
+    self
+  }
+
+  pub fn note(self, message: impl Into<String>) -> Self {
+    self.child(DiagnosticLevel::Note, None, message)
+  }
+
+  pub fn span_note(self, span: Span, message: impl Into<String>) -> Self {
+    self.child(DiagnosticLevel::Note, Some(span), message)
+  }
+
+  pub fn help(self, message: impl Into<String>) -> Self {
+    self.child(DiagnosticLevel::Help, None, message)
+  }
+
+  pub fn span_help(self, span: Span, message: impl Into<String>) -> Self {
+    self.child(DiagnosticLevel::Help, Some(span), message)
+  }
+
+  pub fn span(&self) -> Span {
+    self.span
+  }
+
+  pub fn into_error(self) -> syn::Error {
+    let mut message = self.message;
+
+    for child in self.children.iter().filter(|c| c.span.is_none()) {
+      message.push_str(&format!("\n\n{}: {}", child.level, child.message));
+    }
+
+    let mut error = match self.tokens {
+      Some(tokens) => syn::Error::new_spanned(tokens, message),
+      None => syn::Error::new(self.span, message),
+    };
+
+    for child in self.children {
+      if let Some(span) = child.span {
+        error.combine(syn::Error::new(
+          span,
+          format!("{}: {}", child.level, child.message),
+        ));
+      }
+    }
+
+    error
+  }
+}
+
+impl From<Diagnostic> for syn::Error {
+  fn from(value: Diagnostic) -> Self {
+    value.into_error()
+  }
+}
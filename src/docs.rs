@@ -0,0 +1,165 @@
+use crate::*;
+
+#[derive(Debug, Clone)]
+pub enum DocFragment {
+  /// A regular `#[doc = "..."]` attribute, which is what `///` comments desugar to.
+  Lit(LitStr),
+  /// A `#[doc = include_str!("...")]` attribute. Holds the path passed to the macro.
+  IncludeStr(LitStr),
+  /// Any other expression, such as `#[doc = concat!(...)]`, or an `include_str!` invocation
+  /// whose path is not a plain literal (like `include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))`).
+  Expr(Expr),
+}
+
+/// The doc comments extracted from a list of attributes.
+#[derive(Debug, Clone, Default)]
+pub struct Docs {
+  pub fragments: Vec<DocFragment>,
+}
+
+impl Docs {
+  pub fn from_attrs(attrs: &[Attribute]) -> Self {
+    let mut fragments = Vec::new();
+
+    for attr in attrs {
+      if !attr.path().is_ident("doc") {
+        continue;
+      }
+
+      // Skips things like `#[doc(hidden)]`
+      let Meta::NameValue(nv) = &attr.meta else {
+        continue;
+      };
+
+      let fragment = match &nv.value {
+        Expr::Lit(expr_lit) if let Lit::Str(lit) = &expr_lit.lit => DocFragment::Lit(lit.clone()),
+        Expr::Macro(expr_macro)
+          if is_include_str(&expr_macro.mac)
+            && let Ok(path) = expr_macro.mac.parse_body::<LitStr>() =>
+        {
+          DocFragment::IncludeStr(path)
+        }
+        other => DocFragment::Expr(other.clone()),
+      };
+
+      fragments.push(fragment);
+    }
+
+    Self { fragments }
+  }
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.fragments.is_empty()
+  }
+
+  /// Returns `true` if any of the docs come from an `include_str!` invocation, even if its path is not a literal.
+  ///
+  /// The contents of those files are not available during expansion, so they are not part of [`Docs::text`].
+  #[must_use]
+  pub fn has_include_str(&self) -> bool {
+    self.fragments.iter().any(|f| match f {
+      DocFragment::IncludeStr(_) => true,
+      DocFragment::Expr(Expr::Macro(expr_macro)) => is_include_str(&expr_macro.mac),
+      _ => false,
+    })
+  }
+
+  pub fn include_paths(&self) -> impl Iterator<Item = &LitStr> {
+    self.fragments.iter().filter_map(|f| {
+      if let DocFragment::IncludeStr(path) = f {
+        Some(path)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Returns the normalised paragraphs of the docs, with the common indentation stripped
+  /// and the lines of each paragraph joined by a newline.
+  pub fn paragraphs(&self) -> Vec<String> {
+    let mut lines: Vec<&str> = Vec::new();
+
+    let values: Vec<String> = self
+      .fragments
+      .iter()
+      .filter_map(|f| {
+        if let DocFragment::Lit(lit) = f {
+          Some(lit.value())
+        } else {
+          None
+        }
+      })
+      .collect();
+
+    for value in &values {
+      // Block comments (`/** */`) end up in a single attribute spanning multiple lines
+      lines.extend(value.split('\n'));
+    }
+
+    let indent = lines
+      .iter()
+      .filter(|line| !line.trim().is_empty())
+      .map(|line| line.chars().take_while(|c| c.is_whitespace()).count())
+      .min()
+      .unwrap_or(0);
+
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for line in lines {
+      if line.trim().is_empty() {
+        if !current.is_empty() {
+          paragraphs.push(current.join("\n"));
+          current.clear();
+        }
+      } else {
+        let line: String = line.chars().skip(indent).collect();
+
+        current.push(line.trim_end().to_string());
+      }
+    }
+
+    if !current.is_empty() {
+      paragraphs.push(current.join("\n"));
+    }
+
+    paragraphs
+  }
+
+  /// Returns the normalised text of the docs, with paragraphs separated by an empty line.
+  pub fn text(&self) -> String {
+    self.paragraphs().join("\n\n")
+  }
+
+  /// Returns the first paragraph of the docs, with its lines joined by a space.
+  pub fn summary(&self) -> Option<String> {
+    self
+      .paragraphs()
+      .first()
+      .map(|p| p.lines().collect::<Vec<_>>().join(" "))
+  }
+}
+
+/// Turns a (possibly multi-line) string into a list of `#[doc]` attributes, one per line,
+/// in the same way as the compiler would desugar `///` comments.
+pub fn doc_attributes(text: &str) -> Vec<Attribute> {
+  text
+    .split('\n')
+    .map(|line| {
+      let line = line.trim_end();
+
+      let line = if line.is_empty() {
+        String::new()
+      } else {
+        format!(" {line}")
+      };
+
+      parse_quote!(#[doc = #line])
+    })
+    .collect()
+}
+
+fn is_include_str(mac: &syn::Macro) -> bool {
+  mac.path.last_segment().ident == "include_str"
+}
@@ -0,0 +1,97 @@
+use syn::DeriveInput;
+
+use crate::*;
+
+/// Parses the input of a derive macro and runs the expansion with a [`ControlFlow`],
+/// turning parsing errors, expansion errors and panics (see [`catch_panics`]) into tokens.
+pub fn expand_derive<T, F>(input: TokenStream2, expansion: F) -> TokenStream2
+where
+  T: ToTokens,
+  F: FnOnce(DeriveInput, &mut ControlFlow) -> syn::Result<T>,
+{
+  ControlFlow::new().run(|control_flow| {
+    let input: DeriveInput = syn::parse2(input)?;
+
+    catch_panics(|| expansion(input, control_flow))
+  })
+}
+
+/// Parses the arguments and the item of an attribute macro and runs the expansion with a [`ControlFlow`].
+///
+/// The dummy is initially set to the untouched item, so that the item is still emitted if the macro fails.
+/// It can be replaced during the expansion (for example after stripping the helper attributes from the item).
+pub fn expand_attribute<A, I, T, F>(
+  args: TokenStream2,
+  item: TokenStream2,
+  expansion: F,
+) -> TokenStream2
+where
+  A: Parse,
+  I: Parse,
+  T: ToTokens,
+  F: FnOnce(A, I, &mut ControlFlow) -> syn::Result<T>,
+{
+  ControlFlow::with_custom_dummy(&item).run(|control_flow| {
+    let args: A = syn::parse2(args)?;
+    let item: I = syn::parse2(item)?;
+
+    catch_panics(|| expansion(args, item, control_flow))
+  })
+}
+
+/// Parses the input of a function-like macro and runs the expansion with a [`ControlFlow`].
+pub fn expand_function<I, T, F>(input: TokenStream2, expansion: F) -> TokenStream2
+where
+  I: Parse,
+  T: ToTokens,
+  F: FnOnce(I, &mut ControlFlow) -> syn::Result<T>,
+{
+  ControlFlow::new().run(|control_flow| {
+    let input: I = syn::parse2(input)?;
+
+    catch_panics(|| expansion(input, control_flow))
+  })
+}
+
+/// The entry point for a derive macro. See [`expand_derive`].
+#[cfg(feature = "proc-macro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proc-macro")))]
+pub fn derive_entry<T, F>(input: proc_macro::TokenStream, expansion: F) -> proc_macro::TokenStream
+where
+  T: ToTokens,
+  F: FnOnce(DeriveInput, &mut ControlFlow) -> syn::Result<T>,
+{
+  expand_derive(input.into(), expansion).into()
+}
+
+/// The entry point for an attribute macro. See [`expand_attribute`].
+#[cfg(feature = "proc-macro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proc-macro")))]
+pub fn attribute_entry<A, I, T, F>(
+  args: proc_macro::TokenStream,
+  item: proc_macro::TokenStream,
+  expansion: F,
+) -> proc_macro::TokenStream
+where
+  A: Parse,
+  I: Parse,
+  T: ToTokens,
+  F: FnOnce(A, I, &mut ControlFlow) -> syn::Result<T>,
+{
+  expand_attribute(args.into(), item.into(), expansion).into()
+}
+
+/// The entry point for a function-like macro. See [`expand_function`].
+#[cfg(feature = "proc-macro")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proc-macro")))]
+pub fn function_entry<I, T, F>(
+  input: proc_macro::TokenStream,
+  expansion: F,
+) -> proc_macro::TokenStream
+where
+  I: Parse,
+  T: ToTokens,
+  F: FnOnce(I, &mut ControlFlow) -> syn::Result<T>,
+{
+  expand_function(input.into(), expansion).into()
+}
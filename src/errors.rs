@@ -0,0 +1,71 @@
+/// A collector for errors, so that all the problems in an input can be reported at once
+/// instead of stopping at the first one.
+#[derive(Debug, Default)]
+pub struct Errors {
+  error: Option<syn::Error>,
+}
+
+impl Errors {
+  pub fn new() -> Self {
+    Self { error: None }
+  }
+
+  pub fn push(&mut self, error: syn::Error) {
+    match &mut self.error {
+      Some(existing) => existing.combine(error),
+      None => self.error = Some(error),
+    }
+  }
+
+  /// Collects the error if the result is an `Err`, or returns the value otherwise.
+  pub fn push_result<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+    match result {
+      Ok(value) => Some(value),
+      Err(error) => {
+        self.push(error);
+        None
+      }
+    }
+  }
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.error.is_none()
+  }
+
+  /// Returns the amount of errors collected so far.
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self
+      .error
+      .as_ref()
+      .map_or(0, |error| error.clone().into_iter().count())
+  }
+
+  /// Returns all of the collected errors combined into a single [`syn::Error`], if there are any.
+  pub fn finish(self) -> syn::Result<()> {
+    match self.error {
+      Some(error) => Err(error),
+      None => Ok(()),
+    }
+  }
+
+  /// Like [`Errors::finish`], but it returns the given value if there are no errors.
+  pub fn finish_with<T>(self, value: T) -> syn::Result<T> {
+    self.finish().map(|_| value)
+  }
+}
+
+impl Extend<syn::Error> for Errors {
+  fn extend<I: IntoIterator<Item = syn::Error>>(&mut self, iter: I) {
+    for error in iter {
+      self.push(error);
+    }
+  }
+}
+
+impl From<syn::Error> for Errors {
+  fn from(error: syn::Error) -> Self {
+    Self { error: Some(error) }
+  }
+}
@@ -1,12 +1,23 @@
 use crate::*;
 
 pub trait ExprExt {
+  /// Returns the inner expression, if this is wrapped in parentheses or in an invisible group.
+  fn strip_parens(&self) -> &Expr;
   fn as_string(&self) -> syn::Result<String>;
+  fn as_lit_str(&self) -> syn::Result<&LitStr>;
   fn as_path(&self) -> syn::Result<&Path>;
   fn as_int<N>(&self) -> syn::Result<N>
   where
     N: FromStr,
     N::Err: Display;
+  fn as_float<N>(&self) -> syn::Result<N>
+  where
+    N: FromStr,
+    N::Err: Display;
+  fn as_bool(&self) -> syn::Result<bool>;
+  fn as_char(&self) -> syn::Result<char>;
+  fn as_byte(&self) -> syn::Result<u8>;
+  fn as_byte_string(&self) -> syn::Result<Vec<u8>>;
   fn as_closure(&self) -> syn::Result<&ExprClosure>;
   fn as_call_or_closure(&self) -> syn::Result<CallOrClosure>;
   fn as_call(&self) -> syn::Result<&ExprCall>;
@@ -15,6 +26,14 @@ pub trait ExprExt {
 }
 
 impl ExprExt for Expr {
+  fn strip_parens(&self) -> &Expr {
+    match self {
+      Expr::Paren(paren) => paren.expr.strip_parens(),
+      Expr::Group(group) => group.expr.strip_parens(),
+      _ => self,
+    }
+  }
+
   fn as_range(&self) -> syn::Result<&ExprRange> {
     if let Expr::Range(range) = &self {
       Ok(range)
@@ -25,8 +44,8 @@ impl ExprExt for Expr {
 
   fn as_path_or_closure(&self) -> syn::Result<PathOrClosure> {
     match self {
-      Expr::Closure(closure) => Ok(PathOrClosure::Closure(closure.to_token_stream())),
-      Expr::Path(expr_path) => Ok(PathOrClosure::Path(expr_path.path.to_token_stream())),
+      Expr::Closure(closure) => Ok(PathOrClosure::Closure(closure.clone())),
+      Expr::Path(expr_path) => Ok(PathOrClosure::Path(expr_path.path.clone())),
       _ => Err(error!(self, "Expected a path or a closure")),
     }
   }
@@ -39,13 +58,49 @@ impl ExprExt for Expr {
     }
   }
   fn as_string(&self) -> syn::Result<String> {
-    if let Expr::Lit(expr_lit) = self && let Lit::Str(value) = &expr_lit.lit {
-      Ok(value.value())
+    Ok(self.as_lit_str()?.value())
+  }
+
+  fn as_lit_str(&self) -> syn::Result<&LitStr> {
+    if let Expr::Lit(expr_lit) = self.strip_parens() && let Lit::Str(value) = &expr_lit.lit {
+      Ok(value)
     } else {
       Err(error!(self, "Expected a string literal"))
     }
   }
 
+  fn as_bool(&self) -> syn::Result<bool> {
+    if let Expr::Lit(expr_lit) = self.strip_parens() && let Lit::Bool(value) = &expr_lit.lit {
+      Ok(value.value)
+    } else {
+      Err(error!(self, "Expected a boolean literal"))
+    }
+  }
+
+  fn as_char(&self) -> syn::Result<char> {
+    if let Expr::Lit(expr_lit) = self.strip_parens() && let Lit::Char(value) = &expr_lit.lit {
+      Ok(value.value())
+    } else {
+      Err(error!(self, "Expected a char literal"))
+    }
+  }
+
+  fn as_byte(&self) -> syn::Result<u8> {
+    if let Expr::Lit(expr_lit) = self.strip_parens() && let Lit::Byte(value) = &expr_lit.lit {
+      Ok(value.value())
+    } else {
+      Err(error!(self, "Expected a byte literal"))
+    }
+  }
+
+  fn as_byte_string(&self) -> syn::Result<Vec<u8>> {
+    if let Expr::Lit(expr_lit) = self.strip_parens() && let Lit::ByteStr(value) = &expr_lit.lit {
+      Ok(value.value())
+    } else {
+      Err(error!(self, "Expected a byte string literal"))
+    }
+  }
+
   fn as_path(&self) -> syn::Result<&Path> {
     if let Expr::Path(expr_path) = self {
       Ok(&expr_path.path)
@@ -59,13 +114,33 @@ impl ExprExt for Expr {
     N: FromStr,
     N::Err: Display,
   {
-    if let Expr::Lit(expr_lit) = self && let Lit::Int(value) = &expr_lit.lit {
-      Ok(value.base10_parse::<N>()?)
+    let (negative, lit) = split_negation(self);
+
+    if let Expr::Lit(expr_lit) = lit && let Lit::Int(value) = &expr_lit.lit {
+      parse_signed(self, negative, value.base10_digits())
     } else {
       Err(error!(self, "Expected an integer literal"))
     }
   }
 
+  fn as_float<N>(&self) -> syn::Result<N>
+  where
+    N: FromStr,
+    N::Err: Display,
+  {
+    let (negative, lit) = split_negation(self);
+
+    if let Expr::Lit(expr_lit) = lit {
+      match &expr_lit.lit {
+        Lit::Float(value) => parse_signed(self, negative, value.base10_digits()),
+        Lit::Int(value) => parse_signed(self, negative, value.base10_digits()),
+        _ => Err(error!(self, "Expected a float literal")),
+      }
+    } else {
+      Err(error!(self, "Expected a float literal"))
+    }
+  }
+
   fn as_closure(&self) -> syn::Result<&ExprClosure> {
     if let Expr::Closure(closure) = self {
       Ok(closure)
@@ -76,9 +151,34 @@ impl ExprExt for Expr {
 
   fn as_call_or_closure(&self) -> syn::Result<CallOrClosure> {
     match self {
-      Expr::Closure(closure) => Ok(CallOrClosure::Closure(closure.to_token_stream())),
-      Expr::Call(call) => Ok(CallOrClosure::Call(call.to_token_stream())),
+      Expr::Closure(closure) => Ok(CallOrClosure::Closure(closure.clone())),
+      Expr::Call(call) => Ok(CallOrClosure::Call(call.clone())),
       _ => Err(error!(self, "Expected a function call or a closure")),
     }
   }
 }
+
+// Syn parses `-5` as a unary negation of the literal `5`
+pub(crate) fn split_negation(expr: &Expr) -> (bool, &Expr) {
+  let expr = expr.strip_parens();
+
+  if let Expr::Unary(unary) = expr && let syn::UnOp::Neg(_) = unary.op {
+    (true, unary.expr.strip_parens())
+  } else {
+    (false, expr)
+  }
+}
+
+fn parse_signed<N>(expr: &Expr, negative: bool, digits: &str) -> syn::Result<N>
+where
+  N: FromStr,
+  N::Err: Display,
+{
+  let result = if negative {
+    format!("-{digits}").parse::<N>()
+  } else {
+    digits.parse::<N>()
+  };
+
+  result.map_err(|e| error!(expr, "{e}"))
+}
@@ -14,6 +14,11 @@ impl<'a> FieldOrVariant<'a> {
     }
   }
 
+  /// Returns the ident of the field or variant, converted with the given [`RenameRule`].
+  pub fn renamed(&self, rule: RenameRule) -> syn::Result<String> {
+    Ok(rule.apply_to_ident(self.ident()?))
+  }
+
   pub fn get_type(&self) -> syn::Result<&Type> {
     let output = match self {
       FieldOrVariant::Field(field) => &field.ty,
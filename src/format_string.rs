@@ -0,0 +1,453 @@
+use std::ops::Range as ByteRange;
+
+use syn::Member;
+
+use crate::*;
+
+/// The argument referred to by a placeholder in a format string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormatArg {
+  /// `{name}`
+  Named(String),
+  /// `{0}`
+  Positional(usize),
+  /// `{}`, with the index that it implicitly refers to.
+  Implicit(usize),
+}
+
+impl FormatArg {
+  // The name of the argument in the generated `format_args!` call
+  fn binding_name(&self) -> String {
+    match self {
+      Self::Named(name) => format!("__{name}"),
+      Self::Positional(index) | Self::Implicit(index) => format!("__{index}"),
+    }
+  }
+
+  /// Returns the field that this argument refers to.
+  pub fn member(&self, span: Span) -> Member {
+    match self {
+      Self::Named(name) => Member::Named(Ident::new(name, span)),
+      Self::Positional(index) | Self::Implicit(index) => Member::Unnamed(syn::Index {
+        index: *index as u32,
+        span,
+      }),
+    }
+  }
+}
+
+/// The width or precision in a format spec.
+#[derive(Debug, Clone)]
+pub enum FormatCount {
+  /// `5`
+  Value(usize),
+  /// `name$`, `1$`, or `*` for the precision (which is represented by an implicit argument).
+  Arg { arg: FormatArg, span: Span },
+}
+
+impl Display for FormatCount {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Value(value) => write!(f, "{value}"),
+      Self::Arg {
+        arg: FormatArg::Named(name),
+        ..
+      } => write!(f, "{name}$"),
+      Self::Arg {
+        arg: FormatArg::Positional(index),
+        ..
+      } => write!(f, "{index}$"),
+      Self::Arg {
+        arg: FormatArg::Implicit(_),
+        ..
+      } => f.write_str("*"),
+    }
+  }
+}
+
+/// The format spec after the `:` in a placeholder, like `>8.2` in `{value:>8.2}`.
+#[derive(Debug, Clone, Default)]
+pub struct FormatSpec {
+  /// The fill, alignment, sign, `#` and `0` flags, as written.
+  pub flags: String,
+  pub width: Option<FormatCount>,
+  pub precision: Option<FormatCount>,
+  /// The formatting trait, like `?` or `x`. Empty for `Display`.
+  pub ty: String,
+}
+
+impl FormatSpec {
+  fn render(&self, count: impl Fn(&FormatCount) -> String) -> String {
+    let mut output = self.flags.clone();
+
+    if let Some(width) = &self.width {
+      output.push_str(&count(width));
+    }
+
+    if let Some(precision) = &self.precision {
+      output.push('.');
+      output.push_str(&count(precision));
+    }
+
+    output.push_str(&self.ty);
+    output
+  }
+}
+
+impl Display for FormatSpec {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.render(ToString::to_string))
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct Placeholder {
+  pub arg: FormatArg,
+  /// The format spec after the `:`, if there is one (for example `>5` in `{0:>5}`).
+  pub spec: Option<FormatSpec>,
+  /// The byte range of the whole placeholder (braces included) inside of the value of the literal.
+  pub range: ByteRange<usize>,
+  /// The span of the placeholder inside of the literal. In a proc macro on stable, this is the span
+  /// of the whole literal (see [`LitStrExt::subspan`]).
+  pub span: Span,
+}
+
+impl Placeholder {
+  /// Returns all the arguments used by this placeholder, including the ones used as width or precision,
+  /// along with their spans.
+  pub fn args(&self) -> impl Iterator<Item = (&FormatArg, Span)> {
+    let counts = self
+      .spec
+      .iter()
+      .flat_map(|spec| [&spec.width, &spec.precision])
+      .filter_map(|count| match count {
+        Some(FormatCount::Arg { arg, span }) => Some((arg, *span)),
+        _ => None,
+      });
+
+    std::iter::once((&self.arg, self.span)).chain(counts)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub enum FormatPiece {
+  /// Literal text, with the `{{` and `}}` escapes already resolved.
+  Literal(String),
+  Placeholder(Placeholder),
+}
+
+/// A parsed format string, like the one in `#[display("{name} is {0:>5}")]`.
+#[derive(Debug, Clone)]
+pub struct FormatString {
+  pub lit: LitStr,
+  pub pieces: Vec<FormatPiece>,
+}
+
+impl Parse for FormatString {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    Self::from_lit(input.parse()?)
+  }
+}
+
+impl FormatString {
+  pub fn from_lit(lit: LitStr) -> syn::Result<Self> {
+    let value = lit.value();
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut implicit_index = 0;
+
+    let mut chars = value.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+      match c {
+        '{' if chars.peek().is_some_and(|(_, next)| *next == '{') => {
+          chars.next();
+          literal.push('{');
+        }
+        '}' if chars.peek().is_some_and(|(_, next)| *next == '}') => {
+          chars.next();
+          literal.push('}');
+        }
+        '}' => {
+          bail_with_span!(
+            lit.subspan(start..start + 1),
+            "Unmatched `}}` in format string, use `}}}}` to escape it"
+          );
+        }
+        '{' => {
+          let end = value[start..]
+            .find('}')
+            .map(|offset| start + offset + 1)
+            .ok_or_else(|| {
+              error_with_span!(
+                lit.subspan(start..value.len()),
+                "Unclosed `{{` in format string, use `{{{{` to escape it"
+              )
+            })?;
+
+          // Skips the inner chars, since they are handled below
+          while chars.peek().is_some_and(|(i, _)| *i < end) {
+            chars.next();
+          }
+
+          let span = lit.subspan(start..end);
+          let inner = &value[start + 1..end - 1];
+
+          let (arg, spec) = match inner.split_once(':') {
+            Some((arg, spec)) => {
+              let offset = start + 1 + arg.len() + 1;
+
+              // Parsed first, since `.*` takes the implicit argument before the one of the placeholder
+              let spec = parse_spec(&lit, spec, offset, &mut implicit_index)?;
+
+              (arg.trim(), Some(spec))
+            }
+            None => (inner.trim(), None),
+          };
+
+          let arg = if arg.is_empty() {
+            implicit_index += 1;
+            FormatArg::Implicit(implicit_index - 1)
+          } else if let Ok(index) = arg.parse::<usize>() {
+            FormatArg::Positional(index)
+          } else if !arg.starts_with("r#") && syn::parse_str::<Ident>(arg).is_ok() {
+            FormatArg::Named(arg.to_string())
+          } else {
+            bail_with_span!(span, "Invalid format argument `{arg}`");
+          };
+
+          if !literal.is_empty() {
+            pieces.push(FormatPiece::Literal(std::mem::take(&mut literal)));
+          }
+
+          pieces.push(FormatPiece::Placeholder(Placeholder {
+            arg,
+            spec,
+            range: start..end,
+            span,
+          }));
+        }
+        c => literal.push(c),
+      }
+    }
+
+    if !literal.is_empty() {
+      pieces.push(FormatPiece::Literal(literal));
+    }
+
+    Ok(Self { lit, pieces })
+  }
+
+  pub fn placeholders(&self) -> impl Iterator<Item = &Placeholder> {
+    self.pieces.iter().filter_map(|piece| {
+      if let FormatPiece::Placeholder(placeholder) = piece {
+        Some(placeholder)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Checks that every placeholder refers to an existing field, reporting all the invalid ones at once.
+  pub fn validate_fields(&self, fields: &Fields) -> syn::Result<()> {
+    let mut errors = Errors::new();
+
+    for (arg, span) in self.placeholders().flat_map(Placeholder::args) {
+      let is_valid = match (arg, fields) {
+        (FormatArg::Named(name), Fields::Named(named)) => named
+          .named
+          .iter()
+          .any(|f| f.ident.as_ref().is_some_and(|ident| ident == name)),
+        (FormatArg::Positional(index) | FormatArg::Implicit(index), Fields::Unnamed(unnamed)) => {
+          *index < unnamed.unnamed.len()
+        }
+        _ => false,
+      };
+
+      if !is_valid {
+        let message = match arg {
+          FormatArg::Named(name) => format!("Unknown field `{name}`"),
+          FormatArg::Positional(index) | FormatArg::Implicit(index) => {
+            format!("Unknown positional field `{index}`")
+          }
+        };
+
+        push_error_with_span!(errors, span, "{message}");
+      }
+    }
+
+    errors.finish()
+  }
+
+  /// Checks the placeholders against the fields of an enum variant, like [`FormatString::validate_fields`].
+  ///
+  /// Unit variants get a dedicated error, since none of the placeholders can be valid.
+  pub fn validate_variant(&self, variant: &Variant) -> syn::Result<()> {
+    if variant.is_unit()
+      && let Some(placeholder) = self.placeholders().next()
+    {
+      bail_with_span!(
+        placeholder.span,
+        "Variant `{}` has no fields",
+        variant.ident
+      );
+    }
+
+    self.validate_fields(&variant.fields)
+  }
+
+  /// Generates a `format_args!` call, where every placeholder refers to a named argument,
+  /// whose value is the binding returned by the callback for the field that it refers to.
+  pub fn to_format_args<F, T>(&self, binding: F) -> TokenStream2
+  where
+    F: Fn(&Member) -> T,
+    T: ToTokens,
+  {
+    let mut format = String::new();
+    let mut args: Vec<(Ident, TokenStream2)> = Vec::new();
+
+    for piece in &self.pieces {
+      match piece {
+        FormatPiece::Literal(text) => format.push_str(&text.replace('{', "{{").replace('}', "}}")),
+        FormatPiece::Placeholder(placeholder) => {
+          format.push('{');
+          format.push_str(&placeholder.arg.binding_name());
+
+          if let Some(spec) = &placeholder.spec {
+            format.push(':');
+            // Every count argument becomes a named one, including the implicit `.*`
+            format.push_str(&spec.render(|count| match count {
+              FormatCount::Value(value) => value.to_string(),
+              FormatCount::Arg { arg, .. } => format!("{}$", arg.binding_name()),
+            }));
+          }
+
+          format.push('}');
+
+          for (arg, span) in placeholder.args() {
+            let name = arg.binding_name();
+
+            if !args.iter().any(|(existing, _)| existing == name.as_str()) {
+              args.push((
+                Ident::new(&name, span),
+                binding(&arg.member(span)).into_token_stream(),
+              ));
+            }
+          }
+        }
+      }
+    }
+
+    let format = LitStr::new(&format, self.lit.span());
+    let args = args.iter().map(|(name, value)| quote! { #name = #value });
+
+    quote! { ::core::format_args!(#format #(, #args)*) }
+  }
+}
+
+fn parse_spec(
+  lit: &LitStr,
+  spec: &str,
+  offset: usize,
+  implicit_index: &mut usize,
+) -> syn::Result<FormatSpec> {
+  let mut chars = spec.chars();
+
+  let mut i = match (chars.next(), chars.next()) {
+    (Some(fill), Some('<' | '^' | '>')) => fill.len_utf8() + 1,
+    (Some('<' | '^' | '>'), _) => 1,
+    _ => 0,
+  };
+
+  if spec[i..].starts_with(['+', '-']) {
+    i += 1;
+  }
+
+  if spec[i..].starts_with('#') {
+    i += 1;
+  }
+
+  // `0$` is a width argument rather than the `0` flag
+  if spec[i..].starts_with('0') && !spec[i + 1..].starts_with('$') {
+    i += 1;
+  }
+
+  let flags = spec[..i].to_string();
+
+  let (width, mut i) = parse_count(lit, spec, i, offset);
+
+  let precision = if spec[i..].starts_with('.') {
+    i += 1;
+
+    if spec[i..].starts_with('*') {
+      i += 1;
+      *implicit_index += 1;
+
+      Some(FormatCount::Arg {
+        arg: FormatArg::Implicit(*implicit_index - 1),
+        span: lit.subspan(offset + i - 1..offset + i),
+      })
+    } else {
+      let (precision, next) = parse_count(lit, spec, i, offset);
+
+      if precision.is_none() {
+        bail_with_span!(
+          lit.subspan(offset + i - 1..offset + i),
+          "Expected a precision after `.`"
+        );
+      }
+
+      i = next;
+      precision
+    }
+  } else {
+    None
+  };
+
+  Ok(FormatSpec {
+    flags,
+    width,
+    precision,
+    ty: spec[i..].to_string(),
+  })
+}
+
+// Parses a count starting at `start`, returning it along with the index where it ends
+fn parse_count(
+  lit: &LitStr,
+  spec: &str,
+  start: usize,
+  offset: usize,
+) -> (Option<FormatCount>, usize) {
+  let rest = &spec[start..];
+
+  let len = if rest.starts_with(|c: char| c.is_ascii_digit()) {
+    rest.find(|c: char| !c.is_ascii_digit())
+  } else if rest.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+    rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+  } else {
+    return (None, start);
+  }
+  .unwrap_or(rest.len());
+
+  let text = &rest[..len];
+  let is_arg = rest[len..].starts_with('$');
+
+  let count = match text.parse::<usize>() {
+    Ok(index) if is_arg => FormatArg::Positional(index),
+    Ok(value) => return (Some(FormatCount::Value(value)), start + len),
+    // An identifier not followed by `$` is the formatting trait, like `x` in `{:x}`
+    Err(_) if !is_arg => return (None, start),
+    Err(_) => FormatArg::Named(text.to_string()),
+  };
+
+  let end = start + len + 1;
+
+  (
+    Some(FormatCount::Arg {
+      arg: count,
+      span: lit.subspan(offset + start..offset + end),
+    }),
+    end,
+  )
+}
@@ -1,3 +1,8 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(feature = "proc-macro")]
+extern crate proc_macro;
+
 #[macro_use]
 mod macros;
 
@@ -16,12 +21,44 @@ pub use parsers::*;
 
 mod control_flow;
 pub use control_flow::*;
+mod errors;
+pub use errors::*;
+mod stub_impl;
+pub use stub_impl::*;
+mod warnings;
+pub use warnings::*;
+mod diagnostic;
+pub use diagnostic::*;
+mod entry;
+pub use entry::*;
+mod panic_guard;
+pub use panic_guard::*;
+mod const_eval;
+pub use const_eval::*;
+mod closure;
+pub use closure::*;
+mod substitute;
+pub use substitute::*;
+mod format_string;
+pub use format_string::*;
+mod lit_span;
+pub use lit_span::*;
 mod attributes;
 pub use attributes::*;
+mod strip_attributes;
+pub use strip_attributes::*;
+mod docs;
+pub use docs::*;
+mod cfg;
+pub use cfg::*;
+mod settings;
+pub use settings::*;
+mod case;
+pub use case::*;
 mod field;
 pub use field::*;
 mod enum_variant;
-use std::ops::{Range, RangeFrom};
+use std::ops::Range;
 
 pub use enum_variant::*;
 pub use expr::*;
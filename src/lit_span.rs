@@ -0,0 +1,117 @@
+use std::ops::Range as ByteRange;
+
+use crate::*;
+
+pub trait LitStrExt {
+  /// Maps a byte range inside of the value of the literal to the matching byte range inside of its source code
+  /// (quotes and prefixes included), accounting for raw strings and escapes.
+  ///
+  /// Returns `None` if the range is out of bounds or if it starts or ends in the middle of an escape.
+  fn source_range(&self, range: ByteRange<usize>) -> Option<ByteRange<usize>>;
+  /// Returns the span of a byte range inside of the value of the literal.
+  ///
+  /// This relies on [`proc_macro2::Literal::subspan`], which only returns a span on nightly (where proc-macro2
+  /// enables `proc_macro_span`), or outside of a proc macro with the `span-locations` feature of proc-macro2.
+  /// Inside of a proc macro on stable, it always falls back to the span of the whole literal,
+  /// so errors using these spans underline the entire literal rather than just the range.
+  fn subspan(&self, range: ByteRange<usize>) -> Span;
+}
+
+impl LitStrExt for LitStr {
+  fn source_range(&self, range: ByteRange<usize>) -> Option<ByteRange<usize>> {
+    if range.start > range.end {
+      return None;
+    }
+
+    let boundaries = char_boundaries(&self.token().to_string())?;
+
+    // A line continuation maps one value offset to two source offsets, so the start is placed after it
+    // and the end before it
+    let start = boundaries
+      .iter()
+      .rev()
+      .find(|(value, _)| *value == range.start)?
+      .1;
+    let end = boundaries.iter().find(|(value, _)| *value == range.end)?.1;
+
+    Some(start..end)
+  }
+
+  fn subspan(&self, range: ByteRange<usize>) -> Span {
+    self
+      .source_range(range)
+      .and_then(|range| self.token().subspan(range))
+      .unwrap_or_else(|| self.span())
+  }
+}
+
+// Returns the (value offset, source offset) pairs for each char boundary in the value of the literal
+fn char_boundaries(source: &str) -> Option<Vec<(usize, usize)>> {
+  let mut boundaries = Vec::new();
+
+  if let Some(raw) = source.strip_prefix('r') {
+    let hashes = raw.len() - raw.trim_start_matches('#').len();
+    let content_start = hashes + 2;
+    let content_end = source.rfind('"')?;
+
+    let content = source.get(content_start..content_end)?;
+
+    for (offset, _) in content.char_indices() {
+      boundaries.push((offset, content_start + offset));
+    }
+
+    boundaries.push((content.len(), content_end));
+
+    return Some(boundaries);
+  }
+
+  let content_end = source.rfind('"')?;
+  let content = source.get(1..content_end)?;
+
+  let mut chars = content.char_indices().peekable();
+  let mut value_offset = 0;
+
+  while let Some((offset, c)) = chars.next() {
+    boundaries.push((value_offset, offset + 1));
+
+    if c != '\\' {
+      value_offset += c.len_utf8();
+      continue;
+    }
+
+    let (_, escape) = chars.next()?;
+
+    match escape {
+      'x' => {
+        chars.next()?;
+        chars.next()?;
+        value_offset += 1;
+      }
+      'u' => {
+        let mut code = String::new();
+
+        for (_, c) in chars.by_ref() {
+          match c {
+            '{' | '_' => {}
+            '}' => break,
+            c => code.push(c),
+          }
+        }
+
+        let c = char::from_u32(u32::from_str_radix(&code, 16).ok()?)?;
+        value_offset += c.len_utf8();
+      }
+      '\n' | '\r' => {
+        // The skipped whitespace produces no value, so the next char starts at the same value offset
+        while chars.peek().is_some_and(|(_, c)| c.is_whitespace()) {
+          chars.next();
+        }
+      }
+      _ => value_offset += 1,
+    }
+  }
+
+  boundaries.push((value_offset, content_end));
+
+  Some(boundaries)
+}
@@ -28,6 +28,54 @@ macro_rules! bail_call_site {
   };
 }
 
+#[macro_export]
+macro_rules! ensure {
+  ($cond:expr, $item:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    if !$cond {
+      $crate::bail!($item, $fmt $(, $args)*);
+    }
+  };
+}
+
+#[macro_export]
+macro_rules! ensure_with_span {
+  ($cond:expr, $span:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    if !$cond {
+      $crate::bail_with_span!($span, $fmt $(, $args)*);
+    }
+  };
+}
+
+#[macro_export]
+macro_rules! ensure_call_site {
+  ($cond:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    if !$cond {
+      $crate::bail_call_site!($fmt $(, $args)*);
+    }
+  };
+}
+
+/// Bails if the two values are not equal, appending both of them (with their `Debug` representation) to the message.
+#[macro_export]
+macro_rules! ensure_eq {
+  ($left:expr, $right:expr, $item:expr $(,)?) => {
+    $crate::ensure_eq!($left, $right, $item, "Values are not equal")
+  };
+
+  ($left:expr, $right:expr, $item:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    match (&$left, &$right) {
+      (left, right) => {
+        if left != right {
+          return Err(syn::Error::new_spanned(
+            &$item,
+            format!("{} (left: `{:?}`, right: `{:?}`)", format!($fmt $(, $args)*), left, right)
+          ));
+        }
+      }
+    }
+  };
+}
+
 #[macro_export]
 macro_rules! error {
   ($item:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
@@ -57,3 +105,114 @@ macro_rules! error_call_site {
     )
   };
 }
+
+#[macro_export]
+macro_rules! push_error {
+  ($errors:expr, $item:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    $errors.push(syn::Error::new_spanned(
+      &$item,
+      format!($fmt $(, $args)*)
+    ))
+  };
+}
+
+#[macro_export]
+macro_rules! push_error_with_span {
+  ($errors:expr, $span:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    $errors.push(syn::Error::new(
+      $span,
+      format!($fmt $(, $args)*)
+    ))
+  };
+}
+
+#[macro_export]
+macro_rules! push_error_call_site {
+  ($errors:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    $errors.push(syn::Error::new(
+      proc_macro2::Span::call_site(),
+      format!($fmt $(, $args)*)
+    ))
+  };
+}
+
+#[macro_export]
+macro_rules! push_warning {
+  ($warnings:expr, $item:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    $warnings.push(
+      syn::spanned::Spanned::span(&$item),
+      format!($fmt $(, $args)*)
+    )
+  };
+}
+
+#[macro_export]
+macro_rules! push_warning_with_span {
+  ($warnings:expr, $span:expr, $fmt:literal $(, $args:expr)* $(,)?) => {
+    $warnings.push(
+      $span,
+      format!($fmt $(, $args)*)
+    )
+  };
+}
+
+/// Like [`error!`], but it accepts a list of notes and help messages after a semicolon,
+/// optionally pointing at a different span.
+///
+/// ```
+/// # use syn_utils::error_with_notes;
+/// # let field = quote::quote!(a: i32);
+/// # let other_span = proc_macro2::Span::call_site();
+/// let error = error_with_notes!(
+///   field, "Field `{}` is defined twice", "a";
+///   note(other_span) = "First defined here";
+///   help = "Remove one of the definitions"
+/// );
+/// ```
+#[macro_export]
+macro_rules! error_with_notes {
+  ($item:expr, $fmt:literal $(, $args:expr)* $(; $($children:tt)*)?) => {
+    $crate::__diagnostic_children!(
+      $crate::Diagnostic::spanned(&$item, format!($fmt $(, $args)*));
+      $($($children)*)?
+    )
+    .into_error()
+  };
+}
+
+/// Like [`error_with_span!`], but it accepts a list of notes and help messages after a semicolon,
+/// in the same way as [`error_with_notes!`].
+#[macro_export]
+macro_rules! error_with_span_and_notes {
+  ($span:expr, $fmt:literal $(, $args:expr)* $(; $($children:tt)*)?) => {
+    $crate::__diagnostic_children!(
+      $crate::Diagnostic::new($span, format!($fmt $(, $args)*));
+      $($($children)*)?
+    )
+    .into_error()
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __diagnostic_children {
+  ($diag:expr;) => {
+    $diag
+  };
+
+  ($diag:expr; note($span:expr) = $fmt:literal $(, $args:expr)* $(; $($rest:tt)*)?) => {
+    $crate::__diagnostic_children!($diag.span_note($span, format!($fmt $(, $args)*)); $($($rest)*)?)
+  };
+
+  ($diag:expr; note = $fmt:literal $(, $args:expr)* $(; $($rest:tt)*)?) => {
+    $crate::__diagnostic_children!($diag.note(format!($fmt $(, $args)*)); $($($rest)*)?)
+  };
+
+  ($diag:expr; help($span:expr) = $fmt:literal $(, $args:expr)* $(; $($rest:tt)*)?) => {
+    $crate::__diagnostic_children!($diag.span_help($span, format!($fmt $(, $args)*)); $($($rest)*)?)
+  };
+
+  ($diag:expr; help = $fmt:literal $(, $args:expr)* $(; $($rest:tt)*)?) => {
+    $crate::__diagnostic_children!($diag.help(format!($fmt $(, $args)*)); $($($rest)*)?)
+  };
+}
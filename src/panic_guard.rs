@@ -0,0 +1,103 @@
+use std::{
+  any::Any,
+  cell::{Cell, RefCell},
+  panic::{self, AssertUnwindSafe},
+  sync::Once,
+};
+
+use crate::*;
+
+struct PanicDetails {
+  message: String,
+  location: Option<String>,
+}
+
+thread_local! {
+  static GUARD_DEPTH: Cell<usize> = const { Cell::new(0) };
+  static PANIC_DETAILS: RefCell<Option<PanicDetails>> = const { RefCell::new(None) };
+  static PANIC_SPAN: Cell<Option<Span>> = const { Cell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+// The hook only intercepts panics on threads that are inside of `catch_panics`,
+// and defers to the previous hook for everything else.
+fn install_hook() {
+  INSTALL_HOOK.call_once(|| {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+      let is_guarded = GUARD_DEPTH
+        .try_with(|depth| depth.get() > 0)
+        .unwrap_or(false);
+
+      if is_guarded {
+        let details = PanicDetails {
+          message: payload_message(info.payload()),
+          location: info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        };
+
+        let _ = PANIC_DETAILS.try_with(|d| *d.borrow_mut() = Some(details));
+      } else {
+        previous(info);
+      }
+    }));
+  });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message.to_string()
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message.clone()
+  } else {
+    "unknown panic payload".to_string()
+  }
+}
+
+/// Registers the span that the error should point to, if the expansion currently guarded by [`catch_panics`] panics.
+///
+/// Calling this outside of [`catch_panics`] has no effect.
+pub fn set_panic_span(span: Span) {
+  PANIC_SPAN.set(Some(span));
+}
+
+/// Runs the expansion, catching any unwinding panic and turning it into an error with the panic message and location.
+///
+/// The error points at the span registered with [`set_panic_span`], or at the call site if there is none.
+pub fn catch_panics<T, F>(expansion: F) -> syn::Result<T>
+where
+  F: FnOnce() -> syn::Result<T>,
+{
+  install_hook();
+
+  let outer_span = PANIC_SPAN.take();
+  GUARD_DEPTH.set(GUARD_DEPTH.get() + 1);
+
+  let result = panic::catch_unwind(AssertUnwindSafe(expansion));
+
+  GUARD_DEPTH.set(GUARD_DEPTH.get() - 1);
+  let span = PANIC_SPAN.replace(outer_span);
+
+  match result {
+    Ok(result) => result,
+    Err(payload) => {
+      let details = PANIC_DETAILS.take().unwrap_or_else(|| PanicDetails {
+        message: payload_message(payload.as_ref()),
+        location: None,
+      });
+
+      let message = match details.location {
+        Some(location) => format!("proc macro panicked: {} (at {location})", details.message),
+        None => format!("proc macro panicked: {}", details.message),
+      };
+
+      Err(syn::Error::new(
+        span.unwrap_or_else(Span::call_site),
+        message,
+      ))
+    }
+  }
+}
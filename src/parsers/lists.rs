@@ -0,0 +1,468 @@
+use std::marker::PhantomData;
+
+use crate::*;
+
+/// The delimiter that surrounds a list, like `[...]` for [`Bracketed`]. [`NoDelimiter`] is used for bare lists.
+pub trait ListDelimiter {
+  /// Parses the content inside of the delimiter with the given function, returning the span of the delimiter.
+  fn parse_content<R, F>(input: ParseStream, parser: F) -> syn::Result<(R, Option<Span>)>
+  where
+    F: FnOnce(ParseStream) -> syn::Result<R>;
+  fn surround(tokens: &mut TokenStream2, content: TokenStream2);
+}
+
+/// A bare list, like `a, b, c`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDelimiter;
+/// A list in `[...]`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bracketed;
+/// A list in `(...)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Parenthesized;
+/// A list in `{...}`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Braced;
+
+impl ListDelimiter for NoDelimiter {
+  fn parse_content<R, F>(input: ParseStream, parser: F) -> syn::Result<(R, Option<Span>)>
+  where
+    F: FnOnce(ParseStream) -> syn::Result<R>,
+  {
+    Ok((parser(input)?, None))
+  }
+
+  fn surround(tokens: &mut TokenStream2, content: TokenStream2) {
+    tokens.extend(content);
+  }
+}
+
+macro_rules! impl_list_delimiter {
+  ($($name:ident => $macro:ident, $token:ident),*) => {
+    $(
+      impl ListDelimiter for $name {
+        fn parse_content<R, F>(input: ParseStream, parser: F) -> syn::Result<(R, Option<Span>)>
+        where
+          F: FnOnce(ParseStream) -> syn::Result<R>,
+        {
+          let content;
+          let delimiter = syn::$macro!(content in input);
+
+          Ok((parser(&content)?, Some(delimiter.span.join())))
+        }
+
+        fn surround(tokens: &mut TokenStream2, content: TokenStream2) {
+          token::$token::default().surround(tokens, |tokens| tokens.extend(content));
+        }
+      }
+    )*
+  };
+}
+
+impl_list_delimiter!(
+  Bracketed => bracketed, Bracket,
+  Parenthesized => parenthesized, Paren,
+  Braced => braced, Brace
+);
+
+/// Whether a list can or must end with a separator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSeparator {
+  #[default]
+  Optional,
+  Forbidden,
+  /// Non-empty lists must end with a separator.
+  Required,
+}
+
+/// Constraints for the lists parsed with [`PunctuatedItems::parse_with`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListOptions {
+  pub trailing: TrailingSeparator,
+  pub min: usize,
+  pub max: Option<usize>,
+}
+
+impl ListOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with_trailing(mut self, trailing: TrailingSeparator) -> Self {
+    self.trailing = trailing;
+    self
+  }
+
+  pub fn with_min(mut self, min: usize) -> Self {
+    self.min = min;
+    self
+  }
+
+  pub fn with_max(mut self, max: usize) -> Self {
+    self.max = Some(max);
+    self
+  }
+}
+
+/// A list of items separated by `S` (`,` by default) and surrounded by the delimiter `D` (none by default),
+/// such as `PunctuatedItems<Path, Token![|], Bracketed>` for `[a::b | c]`.
+pub struct PunctuatedItems<T: Parse + ToTokens, S = Token![,], D = NoDelimiter> {
+  /// Each item in the list, along with its span.
+  pub items: Vec<(T, Span)>,
+  _marker: PhantomData<(S, D)>,
+}
+
+impl<T: Parse + ToTokens> PunctuatedItems<T> {
+  /// Creates a comma-separated list from its items, with every span set to [`Span::call_site`].
+  ///
+  /// Lists with a different separator or delimiter can be created with [`From<Vec<T>>`].
+  pub fn new(list: Vec<T>) -> Self {
+    list.into()
+  }
+}
+
+impl<T: Parse + ToTokens, S, D> From<Vec<T>> for PunctuatedItems<T, S, D> {
+  fn from(list: Vec<T>) -> Self {
+    Self {
+      items: list
+        .into_iter()
+        .map(|item| (item, Span::call_site()))
+        .collect(),
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<T: Parse + ToTokens, S, D> PunctuatedItems<T, S, D> {
+  /// Returns the items of the list, without their spans.
+  pub fn values(&self) -> impl Iterator<Item = &T> {
+    self.items.iter().map(|(item, _)| item)
+  }
+
+  /// Returns the span of each item in the list.
+  pub fn spans(&self) -> impl Iterator<Item = Span> {
+    self.items.iter().map(|(_, span)| *span)
+  }
+
+  /// Consumes the list, returning its items without their spans.
+  pub fn into_values(self) -> Vec<T> {
+    self.items.into_iter().map(|(item, _)| item).collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+}
+
+pub type PathList<S = Token![,], D = NoDelimiter> = PunctuatedItems<Path, S, D>;
+pub type IdentList<S = Token![,], D = NoDelimiter> = PunctuatedItems<Ident, S, D>;
+
+impl<T, S, D> PunctuatedItems<T, S, D>
+where
+  T: Parse + ToTokens,
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  /// Parses the list, checking the trailing separator and the amount of items.
+  pub fn parse_with(input: ParseStream, options: &ListOptions) -> syn::Result<Self> {
+    let ((items, trailing_span), delimiter_span) = D::parse_content(input, |content| {
+      let mut items: Vec<(T, Span)> = Vec::new();
+      let mut trailing_span: Option<Span> = None;
+
+      while !content.is_empty() {
+        let item: T = content.parse()?;
+        let span = item.span();
+
+        items.push((item, span));
+        trailing_span = None;
+
+        if content.is_empty() {
+          break;
+        }
+
+        let separator: S = content.parse()?;
+        trailing_span = Some(separator.span());
+      }
+
+      Ok((items, trailing_span))
+    })?;
+
+    let separator = S::default().into_token_stream();
+
+    match (options.trailing, trailing_span, items.last()) {
+      (TrailingSeparator::Forbidden, Some(span), _) => {
+        bail_with_span!(span, "Trailing `{separator}` is not allowed");
+      }
+      (TrailingSeparator::Required, None, Some((_, last))) => {
+        bail_with_span!(*last, "Expected a trailing `{separator}`");
+      }
+      _ => {}
+    };
+
+    if let Some(max) = options.max
+      && let Some((_, extra)) = items.get(max)
+    {
+      bail_with_span!(
+        *extra,
+        "Expected at most {max} item{}, found {}",
+        if max == 1 { "" } else { "s" },
+        items.len()
+      );
+    }
+
+    if items.len() < options.min {
+      let span = delimiter_span
+        .or_else(|| {
+          let (_, first) = items.first()?;
+          let (_, last) = items.last()?;
+
+          first.join(*last).or(Some(*first))
+        })
+        .unwrap_or_else(|| input.span());
+
+      bail_with_span!(
+        span,
+        "Expected at least {} item{}, found {}",
+        options.min,
+        if options.min == 1 { "" } else { "s" },
+        items.len()
+      );
+    }
+
+    Ok(Self {
+      items,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<T, S, D> Parse for PunctuatedItems<T, S, D>
+where
+  T: Parse + ToTokens,
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    Self::parse_with(input, &ListOptions::default())
+  }
+}
+
+impl<T, S, D> ToTokens for PunctuatedItems<T, S, D>
+where
+  T: Parse + ToTokens,
+  S: ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    D::surround(tokens, separated(self.values(), &S::default()));
+  }
+}
+
+fn separated<T: ToTokens>(
+  items: impl IntoIterator<Item = T>,
+  separator: &impl ToTokens,
+) -> TokenStream2 {
+  let mut output = TokenStream2::new();
+
+  for (i, item) in items.into_iter().enumerate() {
+    if i > 0 {
+      separator.to_tokens(&mut output);
+    }
+
+    item.to_tokens(&mut output);
+  }
+
+  output
+}
+
+/// A list of string literals, separated and delimited like [`PunctuatedItems`].
+pub struct StringList<S = Token![,], D = NoDelimiter> {
+  /// Each value in the list, along with the span of its literal.
+  pub items: Vec<(String, Span)>,
+  _marker: PhantomData<(S, D)>,
+}
+
+impl StringList {
+  /// Creates a comma-separated list from its values, with every span set to [`Span::call_site`].
+  ///
+  /// Lists with a different separator or delimiter can be created with [`From<Vec<String>>`].
+  pub fn new(list: Vec<String>) -> Self {
+    list.into()
+  }
+}
+
+impl<S, D> From<Vec<String>> for StringList<S, D> {
+  fn from(list: Vec<String>) -> Self {
+    Self {
+      items: list
+        .into_iter()
+        .map(|item| (item, Span::call_site()))
+        .collect(),
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<S, D> StringList<S, D> {
+  /// Returns the items of the list, without their spans.
+  pub fn values(&self) -> impl Iterator<Item = &String> {
+    self.items.iter().map(|(item, _)| item)
+  }
+
+  /// Returns the span of each item in the list.
+  pub fn spans(&self) -> impl Iterator<Item = Span> {
+    self.items.iter().map(|(_, span)| *span)
+  }
+
+  /// Consumes the list, returning its items without their spans.
+  pub fn into_values(self) -> Vec<String> {
+    self.items.into_iter().map(|(item, _)| item).collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+}
+
+impl<S, D> StringList<S, D>
+where
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  pub fn parse_with(input: ParseStream, options: &ListOptions) -> syn::Result<Self> {
+    let items = PunctuatedItems::<LitStr, S, D>::parse_with(input, options)?;
+
+    Ok(Self {
+      items: items
+        .items
+        .into_iter()
+        .map(|(lit, span)| (lit.value(), span))
+        .collect(),
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<S, D> ToTokens for StringList<S, D>
+where
+  S: ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    D::surround(tokens, separated(self.values(), &S::default()));
+  }
+}
+
+impl<S, D> Parse for StringList<S, D>
+where
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    Self::parse_with(input, &ListOptions::default())
+  }
+}
+
+/// A list of integers, separated and delimited like [`PunctuatedItems`].
+pub struct NumList<S = Token![,], D = NoDelimiter> {
+  /// Each value in the list, along with the span of its literal.
+  pub items: Vec<(i32, Span)>,
+  _marker: PhantomData<(S, D)>,
+}
+
+impl NumList {
+  /// Creates a comma-separated list from its values, with every span set to [`Span::call_site`].
+  ///
+  /// Lists with a different separator or delimiter can be created with [`From<Vec<i32>>`].
+  pub fn new(list: Vec<i32>) -> Self {
+    list.into()
+  }
+}
+
+impl<S, D> From<Vec<i32>> for NumList<S, D> {
+  fn from(list: Vec<i32>) -> Self {
+    Self {
+      items: list
+        .into_iter()
+        .map(|item| (item, Span::call_site()))
+        .collect(),
+      _marker: PhantomData,
+    }
+  }
+}
+
+impl<S, D> NumList<S, D> {
+  /// Returns the items of the list, without their spans.
+  pub fn values(&self) -> impl Iterator<Item = &i32> {
+    self.items.iter().map(|(item, _)| item)
+  }
+
+  /// Returns the span of each item in the list.
+  pub fn spans(&self) -> impl Iterator<Item = Span> {
+    self.items.iter().map(|(_, span)| *span)
+  }
+
+  /// Consumes the list, returning its items without their spans.
+  pub fn into_values(self) -> Vec<i32> {
+    self.items.into_iter().map(|(item, _)| item).collect()
+  }
+
+  pub fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+}
+
+impl<S, D> NumList<S, D>
+where
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  pub fn parse_with(input: ParseStream, options: &ListOptions) -> syn::Result<Self> {
+    let items = PunctuatedItems::<LitInt, S, D>::parse_with(input, options)?;
+
+    Ok(Self {
+      items: items
+        .items
+        .into_iter()
+        .map(|(lit, span)| Ok((lit.base10_parse()?, span)))
+        .collect::<syn::Result<Vec<(i32, Span)>>>()?,
+      _marker: PhantomData,
+    })
+  }
+}
+
+impl<S, D> ToTokens for NumList<S, D>
+where
+  S: ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let list = self
+      .values()
+      .map(|n| proc_macro2::Literal::i32_unsuffixed(*n));
+
+    D::surround(tokens, separated(list, &S::default()));
+  }
+}
+
+impl<S, D> Parse for NumList<S, D>
+where
+  S: Parse + ToTokens + Default,
+  D: ListDelimiter,
+{
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    Self::parse_with(input, &ListOptions::default())
+  }
+}
@@ -1,137 +1,54 @@
-use syn::{token::Comma, RangeLimits};
-
 use crate::*;
 
+mod lists;
+pub use lists::*;
+mod ranges;
+pub use ranges::*;
+
 #[derive(Debug, Clone)]
-pub struct ClosedRangeList {
-  pub list: Vec<Range<i32>>,
+pub enum PathOrClosure {
+  Path(Path),
+  Closure(ExprClosure),
 }
 
-impl Parse for ClosedRangeList {
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-    let mut ranges: Vec<Range<i32>> = Vec::new();
-
-    while !input.is_empty() {
-      let item: Expr = input.parse()?;
-
-      if let Expr::Range(range_expr) = &item {
-        let start = if let Some(start_expr) = &range_expr.start {
-          start_expr.as_int::<i32>()?
-        } else {
-          return Err(input.error("Expected a defined start for this range"));
-        };
-
-        if let Some(end_expr) = &range_expr.end {
-          let mut end = end_expr.as_int::<i32>()?;
-
-          if let RangeLimits::Closed(_) = &range_expr.limits {
-            end += 1;
-          }
-
-          ranges.push(start..end)
-        } else {
-          return Err(input.error("Expected a closed range"));
-        }
-      } else if let Expr::Lit(lit) = &item && let Lit::Int(lit_int) = &lit.lit {
-        let num = lit_int.base10_parse::<i32>()?;
-
-        ranges.push(num..num + 1);
-      } else {
-        return Err(error!(
-          item,
-          "Expected a range (e.g. `1..5`, `10..=15`) or a single number"
-        ));
-      }
-
-      if input.is_empty() {
-        break;
-      }
-
-      let _comma: Comma = input.parse()?;
+impl ToTokens for PathOrClosure {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    match self {
+      PathOrClosure::Path(path) => path.to_tokens(tokens),
+      PathOrClosure::Closure(expr_closure) => expr_closure.to_tokens(tokens),
     }
-
-    ranges.sort_by_key(|range| range.start);
-
-    Ok(Self { list: ranges })
   }
 }
 
-#[derive(Debug, Clone)]
-pub enum GenericRange {
-  Open(RangeFrom<i32>),
-  Closed(Range<i32>),
-}
+impl PathOrClosure {
+  /// Generates a call with the given arguments. Closures are wrapped in parentheses, so that they can be called in place.
+  pub fn invoke<I>(&self, args: I) -> TokenStream2
+  where
+    I: IntoIterator,
+    I::Item: ToTokens,
+  {
+    let args: Vec<I::Item> = args.into_iter().collect();
 
-#[derive(Debug, Clone)]
-pub struct GenericRangeList {
-  pub list: Vec<GenericRange>,
-}
-
-impl Parse for GenericRangeList {
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-    let mut ranges: Vec<GenericRange> = Vec::new();
-
-    while !input.is_empty() {
-      let item: Expr = input.parse()?;
-
-      if let Expr::Range(range_expr) = &item {
-        let start = if let Some(start_expr) = &range_expr.start {
-          start_expr.as_int::<i32>()?
-        } else {
-          return Err(input.error("Expected a defined start for this range"));
-        };
-
-        if let Some(end_expr) = &range_expr.end {
-          let mut end = end_expr.as_int::<i32>()?;
-
-          if let RangeLimits::Closed(_) = &range_expr.limits {
-            end += 1;
-          }
-          ranges.push(GenericRange::Closed(start..end))
-        } else {
-          ranges.push(GenericRange::Open(start..))
-        }
-      } else if let Expr::Lit(lit) = &item && let Lit::Int(lit_int) = &lit.lit {
-        let num = lit_int.base10_parse::<i32>()?;
-
-        ranges.push(GenericRange::Closed(num..num + 1));
-      } else {
-        return Err(error!(
-          item,
-          "Expected a range (e.g. `1..5`, `10..=15`) or a single number"
-        ));
-      }
-
-      if input.is_empty() {
-        break;
-      }
-
-      let _comma: Comma = input.parse()?;
+    match self {
+      PathOrClosure::Path(path) => quote! { #path(#(#args),*) },
+      PathOrClosure::Closure(closure) => quote! { (#closure)(#(#args),*) },
     }
-
-    Ok(Self { list: ranges })
   }
-}
 
-#[derive(Debug, Clone)]
-pub enum PathOrClosure {
-  Path(TokenStream2),
-  Closure(TokenStream2),
-}
-
-impl ToTokens for PathOrClosure {
-  fn to_tokens(&self, tokens: &mut TokenStream2) {
+  /// Checks that the closure takes the expected amount of parameters.
+  /// Paths can't be checked at this stage, so they are always accepted.
+  pub fn check_arity(&self, expected: usize) -> syn::Result<()> {
     match self {
-      PathOrClosure::Path(path) => path.to_tokens(tokens),
-      PathOrClosure::Closure(expr_closure) => expr_closure.to_tokens(tokens),
+      PathOrClosure::Path(_) => Ok(()),
+      PathOrClosure::Closure(closure) => check_closure_arity(closure, expected),
     }
   }
 }
 
 #[derive(Debug, Clone)]
 pub enum CallOrClosure {
-  Call(TokenStream2),
-  Closure(TokenStream2),
+  Call(ExprCall),
+  Closure(ExprClosure),
 }
 
 impl ToTokens for CallOrClosure {
@@ -143,101 +60,55 @@ impl ToTokens for CallOrClosure {
   }
 }
 
-pub struct PunctuatedItems<T: Parse + ToTokens> {
-  pub list: Vec<T>,
-}
-
-pub type PathList = PunctuatedItems<Path>;
-pub type IdentList = PunctuatedItems<Ident>;
-
-impl<T: Parse + ToTokens> Parse for PunctuatedItems<T> {
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-    let mut inner = Vec::new();
-
-    while !input.is_empty() {
-      inner.push(input.parse()?);
+impl CallOrClosure {
+  /// Generates a call with the given arguments. For calls, the arguments are appended after the existing ones,
+  /// while closures are wrapped in parentheses and called in place.
+  pub fn invoke<I>(&self, args: I) -> TokenStream2
+  where
+    I: IntoIterator,
+    I::Item: ToTokens,
+  {
+    let args: Vec<I::Item> = args.into_iter().collect();
 
-      if input.is_empty() {
-        break;
+    match self {
+      CallOrClosure::Call(call) => {
+        let func = &call.func;
+        let all_args = call
+          .args
+          .iter()
+          .map(ToTokens::to_token_stream)
+          .chain(args.iter().map(ToTokens::to_token_stream));
+
+        quote! { #func(#(#all_args),*) }
       }
-      let _comma: Comma = input.parse()?;
+      CallOrClosure::Closure(closure) => quote! { (#closure)(#(#args),*) },
     }
-
-    Ok(Self { list: inner })
-  }
-}
-
-impl<T: Parse + ToTokens> ToTokens for PunctuatedItems<T> {
-  fn to_tokens(&self, tokens: &mut TokenStream2) {
-    let list = &self.list;
-
-    let output = quote! { #(#list),* };
-
-    tokens.extend(output)
   }
-}
-
-pub struct StringList {
-  pub list: Vec<String>,
-}
 
-impl ToTokens for StringList {
-  fn to_tokens(&self, tokens: &mut TokenStream2) {
-    let list = &self.list;
-
-    let output = quote! { #(#list),* };
-
-    tokens.extend(output)
-  }
-}
-
-impl Parse for StringList {
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-    let mut list: Vec<String> = Vec::new();
-
-    while !input.is_empty() {
-      list.push(input.parse::<LitStr>()?.value());
-
-      if input.is_empty() {
-        break;
-      }
-      let _comma: Comma = input.parse()?;
+  /// Checks that the closure takes the expected amount of parameters.
+  /// Calls can't be checked at this stage, so they are always accepted.
+  pub fn check_arity(&self, expected: usize) -> syn::Result<()> {
+    match self {
+      CallOrClosure::Call(_) => Ok(()),
+      CallOrClosure::Closure(closure) => check_closure_arity(closure, expected),
     }
-
-    Ok(Self { list })
   }
 }
 
-pub struct NumList {
-  pub list: Vec<i32>,
-}
+fn check_closure_arity(closure: &ExprClosure, expected: usize) -> syn::Result<()> {
+  let found = closure.inputs.len();
 
-impl ToTokens for NumList {
-  fn to_tokens(&self, tokens: &mut TokenStream2) {
-    let list = self
-      .list
-      .iter()
-      .map(|n| proc_macro2::Literal::i32_unsuffixed(*n));
+  if found != expected {
+    let or1 = &closure.or1_token;
+    let inputs = &closure.inputs;
+    let or2 = &closure.or2_token;
 
-    let output = quote! { #(#list),* };
-
-    tokens.extend(output)
+    bail!(
+      quote! { #or1 #inputs #or2 },
+      "Expected a closure with {expected} parameter{}, found {found}",
+      if expected == 1 { "" } else { "s" }
+    );
   }
-}
-
-impl Parse for NumList {
-  fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-    let mut list: Vec<i32> = Vec::new();
-
-    while !input.is_empty() {
-      list.push(input.parse::<LitInt>()?.base10_parse()?);
 
-      if input.is_empty() {
-        break;
-      }
-      let _comma: Comma = input.parse()?;
-    }
-
-    Ok(Self { list })
-  }
+  Ok(())
 }
@@ -0,0 +1,759 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+use proc_macro2::Literal;
+use syn::{token::Comma, ExprLit, RangeLimits};
+
+use crate::*;
+
+/// A type that can be used as the bound of the ranges in [`ClosedRangeList`] and [`GenericRangeList`].
+///
+/// It's implemented for all the integer types and for `char`.
+pub trait RangeValue: Copy + Ord + Debug + Display {
+  /// The name of the type, as used in literal suffixes and in error messages.
+  const TYPE_NAME: &'static str;
+  const MIN: Self;
+  const MAX: Self;
+
+  /// Parses a single value, rejecting literals with the suffix of a different type.
+  fn from_expr(expr: &Expr) -> syn::Result<Self>;
+  fn checked_succ(self) -> Option<Self>;
+  fn checked_pred(self) -> Option<Self>;
+  /// Returns an unsuffixed literal for this value.
+  fn to_literal(self) -> Literal;
+}
+
+macro_rules! impl_range_value {
+  ($($ty:ident => $literal:ident),*) => {
+    $(
+      impl RangeValue for $ty {
+        const TYPE_NAME: &'static str = stringify!($ty);
+        const MIN: Self = $ty::MIN;
+        const MAX: Self = $ty::MAX;
+
+        fn from_expr(expr: &Expr) -> syn::Result<Self> {
+          parse_int_value(expr)
+        }
+
+        fn checked_succ(self) -> Option<Self> {
+          self.checked_add(1)
+        }
+
+        fn checked_pred(self) -> Option<Self> {
+          self.checked_sub(1)
+        }
+
+        fn to_literal(self) -> Literal {
+          Literal::$literal(self)
+        }
+      }
+    )*
+  };
+}
+
+impl_range_value!(
+  i8 => i8_unsuffixed,
+  i16 => i16_unsuffixed,
+  i32 => i32_unsuffixed,
+  i64 => i64_unsuffixed,
+  i128 => i128_unsuffixed,
+  isize => isize_unsuffixed,
+  u8 => u8_unsuffixed,
+  u16 => u16_unsuffixed,
+  u32 => u32_unsuffixed,
+  u64 => u64_unsuffixed,
+  u128 => u128_unsuffixed,
+  usize => usize_unsuffixed
+);
+
+impl RangeValue for char {
+  const TYPE_NAME: &'static str = "char";
+  const MIN: Self = '\0';
+  const MAX: Self = char::MAX;
+
+  fn from_expr(expr: &Expr) -> syn::Result<Self> {
+    expr.as_char()
+  }
+
+  // Skips the surrogate range, which doesn't contain valid chars
+  fn checked_succ(self) -> Option<Self> {
+    match self {
+      '\u{D7FF}' => Some('\u{E000}'),
+      c => char::from_u32(c as u32 + 1),
+    }
+  }
+
+  fn checked_pred(self) -> Option<Self> {
+    match self {
+      '\0' => None,
+      '\u{E000}' => Some('\u{D7FF}'),
+      c => char::from_u32(c as u32 - 1),
+    }
+  }
+
+  fn to_literal(self) -> Literal {
+    Literal::character(self)
+  }
+}
+
+fn parse_int_value<N>(expr: &Expr) -> syn::Result<N>
+where
+  N: RangeValue + FromStr,
+  N::Err: Display,
+{
+  let (_, inner) = split_negation(expr);
+
+  let Expr::Lit(ExprLit {
+    lit: Lit::Int(lit), ..
+  }) = inner
+  else {
+    bail!(expr, "Expected an integer literal");
+  };
+
+  let suffix = lit.suffix();
+
+  if !suffix.is_empty() && suffix != N::TYPE_NAME {
+    bail!(
+      lit,
+      "Expected a `{}` value, found a `{suffix}` literal",
+      N::TYPE_NAME
+    );
+  }
+
+  expr
+    .as_int::<N>()
+    .map_err(|_| error!(expr, "Value is out of range for `{}`", N::TYPE_NAME))
+}
+
+/// A range with both of its bounds included, along with the span of the expression that it was parsed from.
+#[derive(Debug, Clone, Copy)]
+pub struct ClosedRange<T> {
+  pub start: T,
+  /// The last value in the range (inclusive).
+  pub end: T,
+  pub span: Span,
+}
+
+impl<T: RangeValue> ClosedRange<T> {
+  pub fn new(start: T, end: T, span: Span) -> Self {
+    Self { start, end, span }
+  }
+
+  pub fn single(value: T, span: Span) -> Self {
+    Self::new(value, value, span)
+  }
+
+  pub fn as_inclusive(&self) -> RangeInclusive<T> {
+    self.start..=self.end
+  }
+
+  /// Returns the equivalent half-open range, or `None` if the end is the maximum value of the type.
+  pub fn as_exclusive(&self) -> Option<Range<T>> {
+    Some(self.start..self.end.checked_succ()?)
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    self.start <= value && value <= self.end
+  }
+
+  pub fn overlaps(&self, other: &Self) -> bool {
+    self.start <= other.end && other.start <= self.end
+  }
+
+  /// Returns the range as a pattern (`start..=end`, or just `start` for single values).
+  pub fn to_pattern(&self) -> TokenStream2 {
+    let start = spanned_literal(self.start, self.span);
+
+    if self.start == self.end {
+      quote! { #start }
+    } else {
+      let end = spanned_literal(self.end, self.span);
+
+      quote! { #start..=#end }
+    }
+  }
+
+  // Adjacent ranges like `1..=4` and `5..=8` can be merged, even if they don't overlap
+  fn touches(&self, other: &Self) -> bool {
+    self.overlaps(other)
+      || self.end.checked_succ() == Some(other.start)
+      || other.end.checked_succ() == Some(self.start)
+  }
+}
+
+impl<T: RangeValue> Display for ClosedRange<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.start == self.end {
+      write!(f, "{:?}", self.start)
+    } else {
+      write!(f, "{:?}..={:?}", self.start, self.end)
+    }
+  }
+}
+
+// The span is not taken into account
+impl<T: PartialEq> PartialEq for ClosedRange<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.start == other.start && self.end == other.end
+  }
+}
+
+impl<T: Eq> Eq for ClosedRange<T> {}
+
+/// A list of closed ranges or single values, like `1..5, 10..=15, 20`, sorted by their start.
+#[derive(Debug, Clone)]
+pub struct ClosedRangeList<T = i32> {
+  pub list: Vec<ClosedRange<T>>,
+}
+
+impl<T: RangeValue> Parse for ClosedRangeList<T> {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let mut ranges: Vec<ClosedRange<T>> = Vec::new();
+
+    parse_range_items(input, |item| {
+      match GenericRange::from_expr(&item, CLOSED_RANGE_SYNTAX)? {
+        GenericRange::Closed(range) => ranges.push(range),
+        _ => return Err(syntax_error(&item, CLOSED_RANGE_SYNTAX)),
+      };
+
+      Ok(())
+    })?;
+
+    ranges.sort_by_key(|range| range.start);
+
+    Ok(Self { list: ranges })
+  }
+}
+
+impl<T: RangeValue> ClosedRangeList<T> {
+  pub fn new(list: Vec<ClosedRange<T>>) -> Self {
+    Self { list }
+  }
+
+  /// Sorts the ranges and merges the ones that overlap or are adjacent, so that `1..=4, 3, 5..8` becomes `1..=7`.
+  pub fn normalize(&mut self) {
+    self.list.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<ClosedRange<T>> = Vec::with_capacity(self.list.len());
+
+    for range in self.list.drain(..) {
+      match merged.last_mut() {
+        Some(last) if last.touches(&range) => {
+          last.end = last.end.max(range.end);
+          last.span = last.span.join(range.span).unwrap_or(last.span);
+        }
+        _ => merged.push(range),
+      }
+    }
+
+    self.list = merged;
+  }
+
+  pub fn normalized(&self) -> Self {
+    let mut list = self.clone();
+    list.normalize();
+    list
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    self.list.iter().any(|range| range.contains(value))
+  }
+
+  /// Returns `true` if any of the ranges in this list overlaps with any of the ranges in the other one.
+  pub fn overlaps_with(&self, other: &Self) -> bool {
+    self
+      .list
+      .iter()
+      .any(|range| other.list.iter().any(|o| range.overlaps(o)))
+  }
+
+  /// Checks that none of the ranges in this list overlap with each other.
+  /// Each overlap produces an error that points at both of the ranges involved.
+  pub fn check_overlaps(&self) -> syn::Result<()> {
+    let mut sorted: Vec<&ClosedRange<T>> = self.list.iter().collect();
+    sorted.sort_by_key(|range| range.start);
+
+    let mut errors = Errors::new();
+    // The range with the furthest end so far, which is the only one that a later range could overlap with
+    let mut furthest: Option<&ClosedRange<T>> = None;
+
+    for range in sorted {
+      if let Some(prev) = furthest
+        && prev.overlaps(range)
+      {
+        errors.push(overlap_error(range, prev));
+      }
+
+      if furthest.is_none_or(|prev| range.end > prev.end) {
+        furthest = Some(range);
+      }
+    }
+
+    errors.finish()
+  }
+
+  /// Checks that none of the ranges in this list overlap with the ranges in the other one.
+  pub fn check_overlaps_with(&self, other: &Self) -> syn::Result<()> {
+    let mut errors = Errors::new();
+
+    for range in &self.list {
+      for other_range in other.list.iter().filter(|o| range.overlaps(o)) {
+        errors.push(overlap_error(range, other_range));
+      }
+    }
+
+    errors.finish()
+  }
+
+  /// Checks that the value is not contained in any of the ranges, pointing at both the value and the range if it is.
+  pub fn check_excludes(&self, value: T, span: Span) -> syn::Result<()> {
+    match self.list.iter().find(|range| range.contains(value)) {
+      Some(range) => Err(
+        Diagnostic::new(
+          span,
+          format!("Value `{value:?}` is within the range `{range}`"),
+        )
+        .span_note(range.span, "Range defined here")
+        .into_error(),
+      ),
+      None => Ok(()),
+    }
+  }
+
+  /// Returns the values contained in both lists, keeping the spans of the ranges in this list.
+  pub fn intersection(&self, other: &Self) -> Self {
+    let other = other.normalized();
+    let mut list = Vec::new();
+
+    for range in &self.normalized().list {
+      for other_range in other.list.iter().filter(|o| range.overlaps(o)) {
+        list.push(ClosedRange::new(
+          range.start.max(other_range.start),
+          range.end.min(other_range.end),
+          range.span,
+        ));
+      }
+    }
+
+    Self { list }
+  }
+
+  pub fn union(&self, other: &Self) -> Self {
+    let mut list = Self {
+      list: self.list.iter().chain(other.list.iter()).copied().collect(),
+    };
+
+    list.normalize();
+    list
+  }
+
+  /// Returns the values in this list that are not contained in the other one, keeping the spans of the ranges in this list.
+  pub fn difference(&self, other: &Self) -> Self {
+    let other = other.normalized();
+    let mut list = Vec::new();
+
+    for range in &self.normalized().list {
+      // The first value of the range that hasn't been removed yet, or `None` if the whole rest was removed
+      let mut cursor = Some(range.start);
+
+      for removed in other.list.iter().filter(|o| range.overlaps(o)) {
+        let Some(start) = cursor else {
+          break;
+        };
+
+        if removed.start > start {
+          list.push(ClosedRange::new(
+            start,
+            // Can't underflow, since it's greater than another value
+            removed.start.checked_pred().unwrap(),
+            range.span,
+          ));
+        }
+
+        cursor = removed.end.checked_succ();
+      }
+
+      if let Some(start) = cursor
+        && start <= range.end
+      {
+        list.push(ClosedRange::new(start, range.end, range.span));
+      }
+    }
+
+    Self { list }
+  }
+
+  /// Returns the values within the bounds that are not contained in this list
+  /// (for example, the numbers that are still available after excluding the reserved ones).
+  pub fn complement_within(&self, bounds: RangeInclusive<T>) -> Self {
+    let bounds = ClosedRange::new(*bounds.start(), *bounds.end(), Span::call_site());
+
+    Self { list: vec![bounds] }.difference(self)
+  }
+}
+
+/// Methods to generate code from the list.
+///
+/// The output is always normalized (see [`ClosedRangeList::normalize`]), so that the generated patterns
+/// never trigger unreachable pattern warnings.
+impl<T: RangeValue> ClosedRangeList<T> {
+  /// Returns a pattern that matches every value in the list, like `1..=4 | 10 | 20..=30`.
+  ///
+  /// An empty list produces no tokens, since there is no pattern that never matches.
+  pub fn to_pattern(&self) -> TokenStream2 {
+    let normalized = self.normalized();
+    let patterns = normalized.list.iter().map(ClosedRange::to_pattern);
+
+    quote! { #(#patterns)|* }
+  }
+
+  /// Returns a boolean expression that checks if the given value is contained in the list.
+  pub fn to_contains_expr(&self, value: impl ToTokens) -> TokenStream2 {
+    if self.list.is_empty() {
+      return quote! { false };
+    }
+
+    let pattern = self.to_pattern();
+
+    quote! { ::core::matches!(#value, #pattern) }
+  }
+
+  /// Returns an array of `RangeInclusive`, like `[1..=4, 10..=10]`.
+  pub fn to_array(&self) -> TokenStream2 {
+    let ranges = self.normalized().list.into_iter().map(|range| {
+      let start = spanned_literal(range.start, range.span);
+      let end = spanned_literal(range.end, range.span);
+
+      quote! { #start..=#end }
+    });
+
+    quote! { [#(#ranges),*] }
+  }
+
+  /// Returns a constant holding the array generated by [`ClosedRangeList::to_array`].
+  pub fn to_const(&self, name: &Ident) -> TokenStream2 {
+    let ty = Ident::new(T::TYPE_NAME, Span::call_site());
+    let len = self.normalized().list.len();
+    let array = self.to_array();
+
+    quote! { const #name: [::core::ops::RangeInclusive<#ty>; #len] = #array; }
+  }
+}
+
+/// Emits the list as a pattern, like [`ClosedRangeList::to_pattern`].
+impl<T: RangeValue> ToTokens for ClosedRangeList<T> {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    tokens.extend(self.to_pattern());
+  }
+}
+
+fn spanned_literal<T: RangeValue>(value: T, span: Span) -> proc_macro2::Literal {
+  let mut literal = value.to_literal();
+  literal.set_span(span);
+  literal
+}
+
+fn overlap_error<T: RangeValue>(range: &ClosedRange<T>, other: &ClosedRange<T>) -> syn::Error {
+  Diagnostic::new(
+    range.span,
+    format!("Range `{range}` overlaps with `{other}`"),
+  )
+  .span_note(other.span, format!("`{other}` is defined here"))
+  .into_error()
+}
+
+/// A range in any of the forms supported in patterns, with the bounds being inclusive.
+#[derive(Debug, Clone, Copy)]
+pub enum GenericRange<T = i32> {
+  /// `start..`
+  From {
+    start: T,
+    span: Span,
+  },
+  /// `..end` or `..=end`, where `end` is the last value in the range.
+  To {
+    end: T,
+    span: Span,
+  },
+  /// `..`
+  Full {
+    span: Span,
+  },
+  Closed(ClosedRange<T>),
+}
+
+impl<T: RangeValue> GenericRange<T> {
+  /// Creates a range from its inclusive bounds, where `None` means unbounded.
+  pub fn from_bounds(start: Option<T>, end: Option<T>, span: Span) -> Self {
+    match (start, end) {
+      (Some(start), Some(end)) => Self::Closed(ClosedRange::new(start, end, span)),
+      (Some(start), None) => Self::From { start, span },
+      (None, Some(end)) => Self::To { end, span },
+      (None, None) => Self::Full { span },
+    }
+  }
+
+  /// Returns the first value in the range, or `None` if it's unbounded at the start.
+  pub fn start(&self) -> Option<T> {
+    match self {
+      Self::From { start, .. } => Some(*start),
+      Self::Closed(range) => Some(range.start),
+      Self::To { .. } | Self::Full { .. } => None,
+    }
+  }
+
+  /// Returns the last value in the range, or `None` if it's unbounded at the end.
+  pub fn end(&self) -> Option<T> {
+    match self {
+      Self::To { end, .. } => Some(*end),
+      Self::Closed(range) => Some(range.end),
+      Self::From { .. } | Self::Full { .. } => None,
+    }
+  }
+
+  pub fn span(&self) -> Span {
+    match self {
+      Self::From { span, .. } | Self::To { span, .. } | Self::Full { span } => *span,
+      Self::Closed(range) => range.span,
+    }
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    self.start().is_none_or(|start| start <= value) && self.end().is_none_or(|end| value <= end)
+  }
+
+  pub fn to_pattern(&self) -> TokenStream2 {
+    match self {
+      Self::From { start, span } => {
+        let start = spanned_literal(*start, *span);
+
+        quote! { #start.. }
+      }
+      Self::To { end, span } => {
+        let end = spanned_literal(*end, *span);
+
+        quote! { ..=#end }
+      }
+      Self::Full { span } => quote_spanned! {*span=> _ },
+      Self::Closed(range) => range.to_pattern(),
+    }
+  }
+
+  // Merges a range that starts at or after this one, if they overlap or are adjacent
+  fn merge(&self, next: &Self) -> Option<Self> {
+    let touches = match (self.end().and_then(T::checked_succ), next.start()) {
+      (Some(succ), Some(next_start)) => next_start <= succ,
+      _ => true,
+    };
+
+    if !touches {
+      return None;
+    }
+
+    let end = match (self.end(), next.end()) {
+      (Some(end), Some(next_end)) => Some(end.max(next_end)),
+      _ => None,
+    };
+
+    let span = self.span().join(next.span()).unwrap_or(self.span());
+
+    Some(Self::from_bounds(self.start(), end, span))
+  }
+
+  fn from_expr(item: &Expr, syntax: &str) -> syn::Result<Self> {
+    let Expr::Range(range_expr) = item else {
+      let value = parse_bound(item, syntax)?;
+
+      return Ok(Self::Closed(ClosedRange::single(value, item.span())));
+    };
+
+    let start = range_expr
+      .start
+      .as_deref()
+      .map(|expr| parse_bound(expr, syntax))
+      .transpose()?;
+
+    let end = match &range_expr.end {
+      Some(end_expr) => {
+        let end: T = parse_bound(end_expr, syntax)?;
+
+        let end = match range_expr.limits {
+          RangeLimits::Closed(_) => Some(end),
+          RangeLimits::HalfOpen(_) => end.checked_pred(),
+        };
+
+        match end {
+          Some(end) if start.is_none_or(|start| start <= end) => Some(end),
+          _ => bail!(item, "This range is empty"),
+        }
+      }
+      None => None,
+    };
+
+    Ok(Self::from_bounds(start, end, item.span()))
+  }
+}
+
+/// A list of ranges in any of the forms supported in patterns, like `..=0, 5..10, 20, 100..`.
+#[derive(Debug, Clone)]
+pub struct GenericRangeList<T = i32> {
+  pub list: Vec<GenericRange<T>>,
+}
+
+impl<T: RangeValue> Parse for GenericRangeList<T> {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let mut ranges: Vec<GenericRange<T>> = Vec::new();
+
+    parse_range_items(input, |item| {
+      ranges.push(GenericRange::from_expr(&item, GENERIC_RANGE_SYNTAX)?);
+
+      Ok(())
+    })?;
+
+    Ok(Self { list: ranges })
+  }
+}
+
+impl<T: RangeValue> GenericRangeList<T> {
+  /// Sorts the ranges and merges the ones that overlap or are adjacent, like [`ClosedRangeList::normalize`].
+  pub fn normalize(&mut self) {
+    self.list.sort_by_key(|range| range.start());
+
+    let mut merged: Vec<GenericRange<T>> = Vec::with_capacity(self.list.len());
+
+    for range in self.list.drain(..) {
+      if let Some(last) = merged.last_mut()
+        && let Some(joined) = last.merge(&range)
+      {
+        *last = joined;
+      } else {
+        merged.push(range);
+      }
+    }
+
+    self.list = merged;
+  }
+
+  pub fn normalized(&self) -> Self {
+    let mut list = self.clone();
+    list.normalize();
+    list
+  }
+
+  pub fn contains(&self, value: T) -> bool {
+    self.list.iter().any(|range| range.contains(value))
+  }
+
+  /// Returns a normalized pattern that matches every value in the list, like `1..=4 | 10 | 20..`.
+  ///
+  /// An empty list produces no tokens, since there is no pattern that never matches.
+  pub fn to_pattern(&self) -> TokenStream2 {
+    let normalized = self.normalized();
+    let patterns = normalized.list.iter().map(GenericRange::to_pattern);
+
+    quote! { #(#patterns)|* }
+  }
+
+  /// Returns a boolean expression that checks if the given value is contained in the list.
+  pub fn to_contains_expr(&self, value: impl ToTokens) -> TokenStream2 {
+    if self.list.is_empty() {
+      return quote! { false };
+    }
+
+    let pattern = self.to_pattern();
+
+    quote! { ::core::matches!(#value, #pattern) }
+  }
+
+  /// Returns an array of `(Bound, Bound)` tuples, which implement `RangeBounds`
+  /// (since open and closed ranges have different types).
+  pub fn to_array(&self) -> TokenStream2 {
+    let bound = |value: Option<T>, span: Span| match value {
+      Some(value) => {
+        let value = spanned_literal(value, span);
+
+        quote! { ::core::ops::Bound::Included(#value) }
+      }
+      None => quote! { ::core::ops::Bound::Unbounded },
+    };
+
+    let ranges = self.normalized().list.into_iter().map(|range| {
+      let start = bound(range.start(), range.span());
+      let end = bound(range.end(), range.span());
+
+      quote! { (#start, #end) }
+    });
+
+    quote! { [#(#ranges),*] }
+  }
+
+  /// Returns a constant holding the array generated by [`GenericRangeList::to_array`].
+  pub fn to_const(&self, name: &Ident) -> TokenStream2 {
+    let ty = Ident::new(T::TYPE_NAME, Span::call_site());
+    let len = self.normalized().list.len();
+    let array = self.to_array();
+
+    quote! {
+      const #name: [(::core::ops::Bound<#ty>, ::core::ops::Bound<#ty>); #len] = #array;
+    }
+  }
+}
+
+/// Emits the list as a pattern, like [`GenericRangeList::to_pattern`].
+impl<T: RangeValue> ToTokens for GenericRangeList<T> {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    tokens.extend(self.to_pattern());
+  }
+}
+
+const CLOSED_RANGE_SYNTAX: &str = "a closed range (`1..5` or `1..=5`) or a single value";
+const GENERIC_RANGE_SYNTAX: &str =
+  "a range (`1..5`, `1..=5`, `1..`, `..5`, `..=5` or `..`) or a single value";
+
+fn syntax_error(expr: &Expr, syntax: &str) -> syn::Error {
+  error!(
+    expr,
+    "Expected {syntax}, with literals or `MIN`/`MAX` constants (like `u8::MAX`) as bounds"
+  )
+}
+
+fn parse_bound<T: RangeValue>(expr: &Expr, syntax: &str) -> syn::Result<T> {
+  match split_negation(expr) {
+    (false, Expr::Path(expr_path))
+      if expr_path.qself.is_none() && expr_path.path.segments.len() == 2 =>
+    {
+      let path = &expr_path.path;
+      let ty = &path.segments[0].ident;
+      let constant = &path.segments[1].ident;
+
+      match constant.to_string().as_str() {
+        "MIN" if ty == T::TYPE_NAME => Ok(T::MIN),
+        "MAX" if ty == T::TYPE_NAME => Ok(T::MAX),
+        _ => bail!(
+          path,
+          "Expected `{0}::MIN` or `{0}::MAX`, found `{1}::{constant}`",
+          T::TYPE_NAME,
+          ty
+        ),
+      }
+    }
+    (_, Expr::Lit(_)) => T::from_expr(expr),
+    _ => Err(syntax_error(expr, syntax)),
+  }
+}
+
+fn parse_range_items<F>(input: ParseStream, mut callback: F) -> syn::Result<()>
+where
+  F: FnMut(Expr) -> syn::Result<()>,
+{
+  while !input.is_empty() {
+    callback(input.parse()?)?;
+
+    if input.is_empty() {
+      break;
+    }
+
+    let _comma: Comma = input.parse()?;
+  }
+
+  Ok(())
+}
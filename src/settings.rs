@@ -0,0 +1,165 @@
+use crate::*;
+
+/// The level at which a setting was defined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SettingLevel {
+  Container,
+  Variant,
+  Field,
+}
+
+impl Display for SettingLevel {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let name = match self {
+      Self::Container => "container",
+      Self::Variant => "variant",
+      Self::Field => "field",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// How the values of a setting defined at different levels should be combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cascade {
+  /// The value at the most specific level wins.
+  Override,
+  /// Every level that defines the setting must agree with the levels above it.
+  Strict,
+}
+
+/// A setting resolved with [`SettingLayers`], along with the level it came from.
+#[derive(Debug, Clone)]
+pub struct Resolved<T> {
+  pub value: T,
+  pub level: SettingLevel,
+  pub span: Span,
+}
+
+/// The metas collected (for example with [`filter_attributes`]) at the container, variant and field level,
+/// used to resolve settings that can be defined at any of those levels.
+#[derive(Debug, Clone, Default)]
+pub struct SettingLayers {
+  pub container: Vec<Meta>,
+  pub variant: Option<Vec<Meta>>,
+  pub field: Option<Vec<Meta>>,
+}
+
+impl SettingLayers {
+  pub fn new(container: Vec<Meta>) -> Self {
+    Self {
+      container,
+      variant: None,
+      field: None,
+    }
+  }
+
+  /// Returns a new set of layers, with the given variant metas below the container ones.
+  pub fn with_variant(&self, metas: Vec<Meta>) -> Self {
+    Self {
+      container: self.container.clone(),
+      variant: Some(metas),
+      field: None,
+    }
+  }
+
+  /// Returns a new set of layers, with the given field metas below the container (and variant) ones.
+  pub fn with_field(&self, metas: Vec<Meta>) -> Self {
+    Self {
+      container: self.container.clone(),
+      variant: self.variant.clone(),
+      field: Some(metas),
+    }
+  }
+
+  /// Resolves the setting with the given key, using the parser to extract its value from each level.
+  ///
+  /// Defining the same key twice at the same level is an error. With [`Cascade::Strict`],
+  /// a value that differs from the one defined at a level above it is also an error.
+  pub fn resolve<T, F>(
+    &self,
+    key: &str,
+    cascade: Cascade,
+    parser: F,
+  ) -> syn::Result<Option<Resolved<T>>>
+  where
+    T: PartialEq,
+    F: Fn(&Meta) -> syn::Result<T>,
+  {
+    let layers = [
+      (SettingLevel::Container, Some(&self.container)),
+      (SettingLevel::Variant, self.variant.as_ref()),
+      (SettingLevel::Field, self.field.as_ref()),
+    ];
+
+    let mut resolved: Option<(Resolved<T>, &Meta)> = None;
+
+    for (level, metas) in layers {
+      let Some(meta) = find_setting(metas.map_or(&[], |m| m.as_slice()), key)? else {
+        continue;
+      };
+
+      let value = parser(meta)?;
+
+      if cascade == Cascade::Strict
+        && let Some((prev, prev_meta)) = &resolved
+        && prev.value != value
+      {
+        let mut error = error!(
+          meta,
+          "`{key}` conflicts with the value defined at the {} level", prev.level
+        );
+        error.combine(error!(prev_meta, "`{key}` is defined here"));
+
+        return Err(error);
+      }
+
+      resolved = Some((
+        Resolved {
+          value,
+          level,
+          span: meta.span(),
+        },
+        meta,
+      ));
+    }
+
+    Ok(resolved.map(|(resolved, _)| resolved))
+  }
+
+  /// Resolves a boolean setting, which can be written either as `key` or as `key = true/false`.
+  pub fn resolve_flag(&self, key: &str, cascade: Cascade) -> syn::Result<Option<Resolved<bool>>> {
+    self.resolve(key, cascade, |meta| match meta {
+      Meta::Path(_) => Ok(true),
+      Meta::NameValue(nv) => {
+        if let Expr::Lit(expr_lit) = &nv.value
+          && let Lit::Bool(value) = &expr_lit.lit
+        {
+          Ok(value.value)
+        } else {
+          bail!(nv.value, "Expected a boolean")
+        }
+      }
+      Meta::List(_) => bail!(meta, "Expected `{key}` or `{key} = <bool>`"),
+    })
+  }
+}
+
+fn find_setting<'a>(metas: &'a [Meta], key: &str) -> syn::Result<Option<&'a Meta>> {
+  let mut found: Option<&Meta> = None;
+
+  for meta in metas {
+    if !meta.path().is_ident(key) {
+      continue;
+    }
+
+    if found.is_some() {
+      bail!(meta, "Duplicate `{key}` setting");
+    }
+
+    found = Some(meta);
+  }
+
+  Ok(found)
+}
@@ -0,0 +1,251 @@
+use syn::{FnArg, ImplItem, Item, Member, Signature, TraitItem};
+
+use crate::*;
+
+/// Where a stripped attribute was found, relative to the item that was walked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttrLocation {
+  /// The item itself.
+  Item,
+  /// An enum variant.
+  Variant(Ident),
+  /// A field of a struct, union or enum variant. `variant` is only populated for enums.
+  Field {
+    variant: Option<Ident>,
+    member: Member,
+  },
+  /// A parameter of a function or method. The index counts the receiver, if there is one.
+  FnParam { method: Option<Ident>, index: usize },
+  /// An associated item (method, const or type) inside of an impl or trait block.
+  AssocItem(Ident),
+}
+
+#[derive(Debug, Clone)]
+pub struct StrippedAttribute {
+  pub attr: Attribute,
+  pub location: AttrLocation,
+}
+
+impl StrippedAttribute {
+  /// Parses the metas inside of the attribute, in the same way as [`filter_attributes`].
+  pub fn metas(&self) -> syn::Result<Vec<Meta>> {
+    parse_attr_metas(&self.attr)
+  }
+}
+
+/// Removes all the attributes whose ident is among `idents` from the item and its fields, variants, params and associated items,
+/// so that it can be re-emitted from an attribute macro without leaving behind any inert helper attributes.
+///
+/// Supported items are structs, enums, unions, functions, impl blocks and traits.
+pub fn strip_attributes(item: &mut Item, idents: &[&str]) -> syn::Result<Vec<StrippedAttribute>> {
+  let mut stripped = Vec::new();
+
+  match item {
+    Item::Struct(item_struct) => {
+      take_attrs(
+        &mut item_struct.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+      strip_fields(&mut item_struct.fields, None, idents, &mut stripped);
+    }
+    Item::Union(item_union) => {
+      take_attrs(
+        &mut item_union.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+
+      for field in item_union.fields.named.iter_mut() {
+        let member = Member::Named(field.require_ident()?.clone());
+
+        take_attrs(
+          &mut field.attrs,
+          idents,
+          AttrLocation::Field {
+            variant: None,
+            member,
+          },
+          &mut stripped,
+        );
+      }
+    }
+    Item::Enum(item_enum) => {
+      take_attrs(
+        &mut item_enum.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+
+      for variant in item_enum.variants.iter_mut() {
+        take_attrs(
+          &mut variant.attrs,
+          idents,
+          AttrLocation::Variant(variant.ident.clone()),
+          &mut stripped,
+        );
+
+        strip_fields(
+          &mut variant.fields,
+          Some(&variant.ident),
+          idents,
+          &mut stripped,
+        );
+      }
+    }
+    Item::Fn(item_fn) => {
+      take_attrs(
+        &mut item_fn.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+      strip_params(&mut item_fn.sig, None, idents, &mut stripped);
+    }
+    Item::Impl(item_impl) => {
+      take_attrs(
+        &mut item_impl.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+
+      for impl_item in item_impl.items.iter_mut() {
+        match impl_item {
+          ImplItem::Fn(impl_fn) => {
+            let ident = impl_fn.sig.ident.clone();
+
+            take_attrs(
+              &mut impl_fn.attrs,
+              idents,
+              AttrLocation::AssocItem(ident.clone()),
+              &mut stripped,
+            );
+            strip_params(&mut impl_fn.sig, Some(&ident), idents, &mut stripped);
+          }
+          ImplItem::Const(impl_const) => take_attrs(
+            &mut impl_const.attrs,
+            idents,
+            AttrLocation::AssocItem(impl_const.ident.clone()),
+            &mut stripped,
+          ),
+          ImplItem::Type(impl_type) => take_attrs(
+            &mut impl_type.attrs,
+            idents,
+            AttrLocation::AssocItem(impl_type.ident.clone()),
+            &mut stripped,
+          ),
+          _ => {}
+        }
+      }
+    }
+    Item::Trait(item_trait) => {
+      take_attrs(
+        &mut item_trait.attrs,
+        idents,
+        AttrLocation::Item,
+        &mut stripped,
+      );
+
+      for trait_item in item_trait.items.iter_mut() {
+        match trait_item {
+          TraitItem::Fn(trait_fn) => {
+            let ident = trait_fn.sig.ident.clone();
+
+            take_attrs(
+              &mut trait_fn.attrs,
+              idents,
+              AttrLocation::AssocItem(ident.clone()),
+              &mut stripped,
+            );
+            strip_params(&mut trait_fn.sig, Some(&ident), idents, &mut stripped);
+          }
+          TraitItem::Const(trait_const) => take_attrs(
+            &mut trait_const.attrs,
+            idents,
+            AttrLocation::AssocItem(trait_const.ident.clone()),
+            &mut stripped,
+          ),
+          TraitItem::Type(trait_type) => take_attrs(
+            &mut trait_type.attrs,
+            idents,
+            AttrLocation::AssocItem(trait_type.ident.clone()),
+            &mut stripped,
+          ),
+          _ => {}
+        }
+      }
+    }
+    _ => bail!(
+      item,
+      "Expected a struct, enum, union, function, impl block or trait"
+    ),
+  };
+
+  Ok(stripped)
+}
+
+fn strip_fields(
+  fields: &mut Fields,
+  variant: Option<&Ident>,
+  idents: &[&str],
+  stripped: &mut Vec<StrippedAttribute>,
+) {
+  for (i, field) in fields.iter_mut().enumerate() {
+    let member = match &field.ident {
+      Some(ident) => Member::Named(ident.clone()),
+      None => Member::Unnamed(i.into()),
+    };
+
+    take_attrs(
+      &mut field.attrs,
+      idents,
+      AttrLocation::Field {
+        variant: variant.cloned(),
+        member,
+      },
+      stripped,
+    );
+  }
+}
+
+fn strip_params(
+  sig: &mut Signature,
+  method: Option<&Ident>,
+  idents: &[&str],
+  stripped: &mut Vec<StrippedAttribute>,
+) {
+  for (index, arg) in sig.inputs.iter_mut().enumerate() {
+    let attrs = match arg {
+      FnArg::Receiver(receiver) => &mut receiver.attrs,
+      FnArg::Typed(pat_type) => &mut pat_type.attrs,
+    };
+
+    take_attrs(
+      attrs,
+      idents,
+      AttrLocation::FnParam {
+        method: method.cloned(),
+        index,
+      },
+      stripped,
+    );
+  }
+}
+
+fn take_attrs(
+  attrs: &mut Vec<Attribute>,
+  idents: &[&str],
+  location: AttrLocation,
+  stripped: &mut Vec<StrippedAttribute>,
+) {
+  for attr in attrs.extract_if(.., |attr| attr_matches(attr, idents)) {
+    stripped.push(StrippedAttribute {
+      attr,
+      location: location.clone(),
+    });
+  }
+}
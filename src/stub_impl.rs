@@ -0,0 +1,50 @@
+use syn::{DeriveInput, Signature};
+
+use crate::*;
+
+/// Generates an `impl Trait for Type` block for the input, where every method is bodied with `unimplemented!()`.
+///
+/// When a derive fails, emitting this next to the errors prevents the follow-on "trait not implemented" errors.
+pub fn stub_trait_impl(
+  input: &DeriveInput,
+  trait_path: &Path,
+  methods: &[Signature],
+) -> TokenStream2 {
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  quote! {
+    #[automatically_derived]
+    #[allow(unused_variables, clippy::all)]
+    impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+      #(
+        #methods {
+          ::core::unimplemented!()
+        }
+      )*
+    }
+  }
+}
+
+impl ControlFlow {
+  /// Creates a [`ControlFlow`] whose dummy is a stub impl generated with [`stub_trait_impl`].
+  pub fn with_stub_impl(input: &DeriveInput, trait_path: &Path, methods: &[Signature]) -> Self {
+    Self {
+      dummy: Some(stub_trait_impl(input, trait_path, methods)),
+    }
+  }
+}
+
+/// Runs a derive expansion, emitting a stub impl of the trait next to the errors if it fails.
+pub fn expand_derive_or_stub<T, F>(
+  input: &DeriveInput,
+  trait_path: &Path,
+  methods: &[Signature],
+  expansion: F,
+) -> TokenStream2
+where
+  T: ToTokens,
+  F: FnOnce(&DeriveInput) -> syn::Result<T>,
+{
+  ControlFlow::with_stub_impl(input, trait_path, methods).run(|_| expansion(input))
+}
@@ -0,0 +1,455 @@
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::{Group, TokenTree};
+use syn::{
+  visit_mut::{self, VisitMut},
+  Arm, BinOp, Block, ExprClosure, ExprForLoop, ExprIf, ExprStruct, ExprWhile, FieldValue, FnArg,
+  ImplItemFn, Item, ItemFn, Macro, Member, Pat, Signature, Stmt, TraitItemFn,
+};
+
+use crate::*;
+
+/// A set of identifier substitutions, used to rewrite user-provided code (like `self` → `value` or `Self` → `MyType`)
+/// before placing it in generated code.
+#[derive(Debug, Clone, Default)]
+pub struct Substitutions {
+  map: HashMap<String, TokenStream2>,
+}
+
+impl Substitutions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn with(mut self, ident: impl Into<String>, replacement: impl ToTokens) -> Self {
+    self.insert(ident, replacement);
+    self
+  }
+
+  pub fn insert(&mut self, ident: impl Into<String>, replacement: impl ToTokens) {
+    self
+      .map
+      .insert(ident.into(), replacement.into_token_stream());
+  }
+
+  pub fn apply_to_expr(&self, expr: &mut Expr) {
+    Substituter::new(self).visit_expr_mut(expr);
+  }
+
+  pub fn apply_to_type(&self, ty: &mut Type) {
+    Substituter::new(self).visit_type_mut(ty);
+  }
+
+  pub fn apply_to_block(&self, block: &mut Block) {
+    Substituter::new(self).visit_block_mut(block);
+  }
+
+  pub fn apply_to_item(&self, item: &mut Item) {
+    Substituter::new(self).visit_item_mut(item);
+  }
+
+  /// Substitutes every matching ident in a raw token stream, for fragments that can't be parsed.
+  ///
+  /// Unlike the other methods, this has no knowledge of the syntax, so it doesn't account for shadowing.
+  pub fn apply_to_tokens(&self, tokens: TokenStream2) -> TokenStream2 {
+    replace_tokens(tokens, &|ident| self.map.get(ident))
+  }
+}
+
+fn replace_tokens<'a, F>(tokens: TokenStream2, lookup: &F) -> TokenStream2
+where
+  F: Fn(&str) -> Option<&'a TokenStream2>,
+{
+  let mut output = TokenStream2::new();
+
+  for tree in tokens {
+    match tree {
+      TokenTree::Ident(ident) => match lookup(&ident.to_string()) {
+        Some(replacement) => output.extend(replacement.clone()),
+        None => output.extend([TokenTree::Ident(ident)]),
+      },
+      TokenTree::Group(group) => {
+        let mut new_group = Group::new(group.delimiter(), replace_tokens(group.stream(), lookup));
+        new_group.set_span(group.span());
+
+        output.extend([TokenTree::Group(new_group)]);
+      }
+      other => output.extend([other]),
+    }
+  }
+
+  output
+}
+
+// Keeps track of the bindings introduced by functions, closures, `let` statements and conditions, match arms
+// and `for` loops, so that a binding with the same name as one of the substituted idents is left alone.
+struct Substituter<'a> {
+  subs: &'a Substitutions,
+  scopes: Vec<HashSet<String>>,
+}
+
+impl<'a> Substituter<'a> {
+  fn new(subs: &'a Substitutions) -> Self {
+    Self {
+      subs,
+      scopes: Vec::new(),
+    }
+  }
+
+  fn lookup(&self, ident: &str) -> Option<&'a TokenStream2> {
+    if self.scopes.iter().any(|scope| scope.contains(ident)) {
+      None
+    } else {
+      self.subs.map.get(ident)
+    }
+  }
+
+  fn lookup_path(&self, path: &Path) -> Option<&'a TokenStream2> {
+    let first = path.segments.first()?;
+
+    if path.leading_colon.is_some() || !first.arguments.is_none() {
+      return None;
+    }
+
+    self.lookup(&first.ident.to_string())
+  }
+
+  fn shadow(&mut self, pat: &Pat) {
+    if let Some(scope) = self.scopes.last_mut() {
+      collect_bindings(pat, scope);
+    }
+  }
+
+  // Visits the condition of an `if`, `while` or match guard, shadowing the bindings of its `let`s
+  // in the current scope, since they are visible in the rest of the chain and in the body
+  fn visit_condition(&mut self, cond: &mut Expr) {
+    match cond {
+      Expr::Let(expr_let) => {
+        self.visit_expr_mut(&mut expr_let.expr);
+        self.visit_pat_mut(&mut expr_let.pat);
+        self.shadow(&expr_let.pat);
+      }
+      Expr::Binary(binary) if matches!(binary.op, BinOp::And(_)) => {
+        self.visit_condition(&mut binary.left);
+        self.visit_condition(&mut binary.right);
+      }
+      Expr::Paren(paren) => self.visit_condition(&mut paren.expr),
+      _ => self.visit_expr_mut(cond),
+    }
+  }
+
+  // Visits a function, with its parameters shadowed inside of the body
+  fn visit_fn(&mut self, sig: &mut Signature, block: Option<&mut Block>) {
+    self.visit_signature_mut(sig);
+
+    let Some(block) = block else {
+      return;
+    };
+
+    self.scopes.push(HashSet::new());
+
+    for input in sig.inputs.iter() {
+      match input {
+        FnArg::Typed(pat_type) => self.shadow(&pat_type.pat),
+        FnArg::Receiver(_) => {
+          if let Some(scope) = self.scopes.last_mut() {
+            scope.insert("self".to_string());
+          }
+        }
+      }
+    }
+
+    self.visit_block_mut(block);
+    self.scopes.pop();
+  }
+}
+
+// `Self::new` with `Self` → `Foo<T>` becomes `<Foo<T>>::new`, which is valid both as an expression and as a type
+fn qualified_path(replacement: &TokenStream2, path: &Path) -> TokenStream2 {
+  let rest = path.segments.iter().skip(1);
+
+  quote! { <#replacement> #(:: #rest)* }
+}
+
+// `Self::A` with `Self` → `Foo<T>` becomes `Foo::<T>::A`, which is also valid in expression and pattern position.
+// Returns `None` if the replacement is not a plain path.
+fn turbofish_path(replacement: &TokenStream2, path: &Path) -> Option<Path> {
+  let Ok(Type::Path(TypePath {
+    qself: None,
+    path: mut new_path,
+  })) = syn::parse2::<Type>(replacement.clone())
+  else {
+    return None;
+  };
+
+  for segment in new_path.segments.iter_mut() {
+    if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+      args.colon2_token = Some(Default::default());
+    }
+  }
+
+  new_path
+    .segments
+    .extend(path.segments.iter().skip(1).cloned());
+
+  Some(new_path)
+}
+
+impl VisitMut for Substituter<'_> {
+  fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    if let Expr::Path(expr_path) = expr
+      && expr_path.qself.is_none()
+      && let Some(replacement) = self.lookup_path(&expr_path.path)
+    {
+      if expr_path.path.segments.len() == 1
+        && let Some(path) = turbofish_path(replacement, &expr_path.path)
+      {
+        expr_path.path = path;
+
+        return;
+      }
+
+      let tokens = if expr_path.path.segments.len() == 1 {
+        replacement.clone()
+      } else {
+        qualified_path(replacement, &expr_path.path)
+      };
+
+      *expr = match syn::parse2::<Expr>(tokens.clone()) {
+        // Wrapping in parentheses preserves the precedence of the original expression
+        Ok(new_expr @ (Expr::Path(_) | Expr::Lit(_) | Expr::Paren(_))) => new_expr,
+        Ok(new_expr) => parse_quote!((#new_expr)),
+        Err(_) => Expr::Verbatim(tokens),
+      };
+
+      return;
+    }
+
+    visit_mut::visit_expr_mut(self, expr);
+  }
+
+  fn visit_type_mut(&mut self, ty: &mut Type) {
+    if let Type::Path(type_path) = ty
+      && type_path.qself.is_none()
+      && let Some(replacement) = self.lookup_path(&type_path.path)
+    {
+      let tokens = if type_path.path.segments.len() == 1 {
+        replacement.clone()
+      } else {
+        qualified_path(replacement, &type_path.path)
+      };
+
+      *ty = syn::parse2(tokens.clone()).unwrap_or(Type::Verbatim(tokens));
+
+      return;
+    }
+
+    visit_mut::visit_type_mut(self, ty);
+  }
+
+  fn visit_expr_struct_mut(&mut self, expr: &mut ExprStruct) {
+    if expr.qself.is_none()
+      && let Some(replacement) = self.lookup_path(&expr.path)
+      && let Some(path) = turbofish_path(replacement, &expr.path)
+    {
+      expr.path = path;
+    }
+
+    for field in expr.fields.iter_mut() {
+      self.visit_field_value_mut(field);
+    }
+
+    if let Some(rest) = &mut expr.rest {
+      self.visit_expr_mut(rest);
+    }
+  }
+
+  fn visit_pat_mut(&mut self, pat: &mut Pat) {
+    let path = match pat {
+      Pat::Path(pat_path) if pat_path.qself.is_none() => Some(&mut pat_path.path),
+      Pat::TupleStruct(pat_tuple_struct) if pat_tuple_struct.qself.is_none() => {
+        Some(&mut pat_tuple_struct.path)
+      }
+      Pat::Struct(pat_struct) if pat_struct.qself.is_none() => Some(&mut pat_struct.path),
+      _ => None,
+    };
+
+    if let Some(path) = path
+      && let Some(replacement) = self.lookup_path(path)
+      && let Some(new_path) = turbofish_path(replacement, path)
+    {
+      *path = new_path;
+    }
+
+    visit_mut::visit_pat_mut(self, pat);
+  }
+
+  fn visit_field_value_mut(&mut self, field: &mut FieldValue) {
+    // The shorthand `Foo { value }` must be expanded before the value can be replaced
+    if field.colon_token.is_none()
+      && let Member::Named(ident) = &field.member
+      && self.lookup(&ident.to_string()).is_some()
+    {
+      field.colon_token = Some(Default::default());
+    }
+
+    visit_mut::visit_field_value_mut(self, field);
+  }
+
+  fn visit_macro_mut(&mut self, mac: &mut Macro) {
+    let tokens = std::mem::take(&mut mac.tokens);
+
+    mac.tokens = replace_tokens(tokens, &|ident| self.lookup(ident));
+  }
+
+  fn visit_expr_closure_mut(&mut self, closure: &mut ExprClosure) {
+    for input in closure.inputs.iter_mut() {
+      self.visit_pat_mut(input);
+    }
+
+    if let syn::ReturnType::Type(_, ty) = &mut closure.output {
+      self.visit_type_mut(ty);
+    }
+
+    self.scopes.push(HashSet::new());
+
+    for input in closure.inputs.iter() {
+      self.shadow(input);
+    }
+
+    self.visit_expr_mut(&mut closure.body);
+    self.scopes.pop();
+  }
+
+  fn visit_block_mut(&mut self, block: &mut Block) {
+    self.scopes.push(HashSet::new());
+
+    for stmt in block.stmts.iter_mut() {
+      if let Stmt::Local(local) = stmt {
+        // The initializer can still refer to the outer binding
+        if let Some(init) = &mut local.init {
+          self.visit_expr_mut(&mut init.expr);
+
+          if let Some((_, diverge)) = &mut init.diverge {
+            self.visit_expr_mut(diverge);
+          }
+        }
+
+        self.visit_pat_mut(&mut local.pat);
+        self.shadow(&local.pat);
+      } else {
+        self.visit_stmt_mut(stmt);
+      }
+    }
+
+    self.scopes.pop();
+  }
+
+  fn visit_arm_mut(&mut self, arm: &mut Arm) {
+    self.visit_pat_mut(&mut arm.pat);
+
+    self.scopes.push(HashSet::new());
+    self.shadow(&arm.pat);
+
+    if let Some((_, guard)) = &mut arm.guard {
+      self.visit_condition(guard);
+    }
+
+    self.visit_expr_mut(&mut arm.body);
+    self.scopes.pop();
+  }
+
+  fn visit_expr_for_loop_mut(&mut self, for_loop: &mut ExprForLoop) {
+    self.visit_expr_mut(&mut for_loop.expr);
+    self.visit_pat_mut(&mut for_loop.pat);
+
+    self.scopes.push(HashSet::new());
+    self.shadow(&for_loop.pat);
+
+    self.visit_block_mut(&mut for_loop.body);
+    self.scopes.pop();
+  }
+
+  fn visit_expr_if_mut(&mut self, expr_if: &mut ExprIf) {
+    self.scopes.push(HashSet::new());
+
+    self.visit_condition(&mut expr_if.cond);
+    self.visit_block_mut(&mut expr_if.then_branch);
+
+    self.scopes.pop();
+
+    // The bindings of the condition are not visible in the `else` branch
+    if let Some((_, else_branch)) = &mut expr_if.else_branch {
+      self.visit_expr_mut(else_branch);
+    }
+  }
+
+  fn visit_expr_while_mut(&mut self, expr_while: &mut ExprWhile) {
+    self.scopes.push(HashSet::new());
+
+    self.visit_condition(&mut expr_while.cond);
+    self.visit_block_mut(&mut expr_while.body);
+
+    self.scopes.pop();
+  }
+
+  fn visit_item_fn_mut(&mut self, item_fn: &mut ItemFn) {
+    for attr in item_fn.attrs.iter_mut() {
+      self.visit_attribute_mut(attr);
+    }
+
+    self.visit_fn(&mut item_fn.sig, Some(&mut item_fn.block));
+  }
+
+  fn visit_impl_item_fn_mut(&mut self, impl_fn: &mut ImplItemFn) {
+    for attr in impl_fn.attrs.iter_mut() {
+      self.visit_attribute_mut(attr);
+    }
+
+    self.visit_fn(&mut impl_fn.sig, Some(&mut impl_fn.block));
+  }
+
+  fn visit_trait_item_fn_mut(&mut self, trait_fn: &mut TraitItemFn) {
+    for attr in trait_fn.attrs.iter_mut() {
+      self.visit_attribute_mut(attr);
+    }
+
+    self.visit_fn(&mut trait_fn.sig, trait_fn.default.as_mut());
+  }
+}
+
+fn collect_bindings(pat: &Pat, bindings: &mut HashSet<String>) {
+  match pat {
+    Pat::Ident(pat_ident) => {
+      bindings.insert(pat_ident.ident.to_string());
+
+      if let Some((_, sub)) = &pat_ident.subpat {
+        collect_bindings(sub, bindings);
+      }
+    }
+    Pat::Or(pat_or) => pat_or
+      .cases
+      .iter()
+      .for_each(|p| collect_bindings(p, bindings)),
+    Pat::Paren(pat_paren) => collect_bindings(&pat_paren.pat, bindings),
+    Pat::Reference(pat_ref) => collect_bindings(&pat_ref.pat, bindings),
+    Pat::Slice(pat_slice) => pat_slice
+      .elems
+      .iter()
+      .for_each(|p| collect_bindings(p, bindings)),
+    Pat::Struct(pat_struct) => pat_struct
+      .fields
+      .iter()
+      .for_each(|f| collect_bindings(&f.pat, bindings)),
+    Pat::Tuple(pat_tuple) => pat_tuple
+      .elems
+      .iter()
+      .for_each(|p| collect_bindings(p, bindings)),
+    Pat::TupleStruct(pat_tuple_struct) => pat_tuple_struct
+      .elems
+      .iter()
+      .for_each(|p| collect_bindings(p, bindings)),
+    Pat::Type(pat_type) => collect_bindings(&pat_type.pat, bindings),
+    _ => {}
+  }
+}
@@ -176,7 +176,9 @@ impl TypeInfo {
             span: typ.span(),
           },
           "HashMap" => {
-            let (k, v) = last_segment.first_two_generics().unwrap();
+            let (k, v) = last_segment
+              .first_two_generics()
+              .ok_or_else(|| error!(path, "Expected HashMap to have two generic arguments"))?;
 
             Self {
               reference: None,
@@ -189,7 +191,9 @@ impl TypeInfo {
             }
           }
           "Box" => {
-            let inner = last_segment.first_generic().unwrap();
+            let inner = last_segment
+              .first_generic()
+              .ok_or_else(|| error!(path, "Expected Box to have a generic argument"))?;
 
             Self {
               reference: None,
@@ -198,7 +202,9 @@ impl TypeInfo {
             }
           }
           "Vec" => {
-            let inner = last_segment.first_generic().unwrap();
+            let inner = last_segment
+              .first_generic()
+              .ok_or_else(|| error!(path, "Expected Vec to have a generic argument"))?;
 
             Self {
               reference: None,
@@ -207,7 +213,9 @@ impl TypeInfo {
             }
           }
           "Option" => {
-            let inner = last_segment.first_generic().unwrap();
+            let inner = last_segment
+              .first_generic()
+              .ok_or_else(|| error!(path, "Expected Option to have a generic argument"))?;
 
             Self {
               reference: None,
@@ -0,0 +1,64 @@
+use crate::*;
+
+#[derive(Debug, Clone)]
+pub struct Warning {
+  pub message: String,
+  pub span: Span,
+}
+
+/// A collector for compile-time warnings.
+///
+/// Since proc macros can't emit warnings on stable, each warning is lowered to the usage of a
+/// `#[deprecated]` constant, which makes the compiler emit a warning with the given message at the given span.
+#[derive(Debug, Clone, Default)]
+pub struct Warnings {
+  pub list: Vec<Warning>,
+}
+
+impl Warnings {
+  pub fn new() -> Self {
+    Self { list: Vec::new() }
+  }
+
+  pub fn push(&mut self, span: Span, message: impl Into<String>) {
+    self.list.push(Warning {
+      message: message.into(),
+      span,
+    });
+  }
+
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.list.is_empty()
+  }
+
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.list.len()
+  }
+}
+
+impl ToTokens for Warning {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    let message = &self.message;
+    let name = Ident::new("macro_warning", self.span);
+
+    tokens.extend(quote_spanned! {self.span=>
+      const _: () = {
+        #[deprecated(note = #message)]
+        #[allow(non_upper_case_globals)]
+        const #name: () = ();
+
+        let _ = #name;
+      };
+    });
+  }
+}
+
+impl ToTokens for Warnings {
+  fn to_tokens(&self, tokens: &mut TokenStream2) {
+    for warning in &self.list {
+      warning.to_tokens(tokens);
+    }
+  }
+}
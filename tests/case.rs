@@ -0,0 +1,53 @@
+use syn::{parse_quote, Field, Ident};
+use syn_utils::{FieldOrVariant, RenameRule};
+
+#[test]
+fn test_rules() {
+  let cases = [
+    (RenameRule::LowerCase, "FooBar", "foobar"),
+    (RenameRule::UpperCase, "foo_bar", "FOO_BAR"),
+    (RenameRule::PascalCase, "foo_bar", "FooBar"),
+    (RenameRule::CamelCase, "FooBar", "fooBar"),
+    (
+      RenameRule::SnakeCase,
+      "HTTPServerError",
+      "http_server_error",
+    ),
+    (RenameRule::ScreamingSnakeCase, "fooBar2", "FOO_BAR2"),
+    (RenameRule::KebabCase, "FooBar", "foo-bar"),
+    (RenameRule::ScreamingKebabCase, "foo_bar", "FOO-BAR"),
+  ];
+
+  for (rule, input, expected) in cases {
+    assert_eq!(rule.apply(input), expected, "{rule} failed for {input}");
+  }
+}
+
+#[test]
+fn test_idents() {
+  let ident: Ident = parse_quote!(r#type_name);
+
+  assert_eq!(RenameRule::CamelCase.apply_to_ident(&ident), "typeName");
+
+  let mut field: Field = parse_quote!(pub r#my_field: i32);
+
+  assert_eq!(
+    FieldOrVariant::Field(&mut field)
+      .renamed(RenameRule::PascalCase)
+      .unwrap(),
+    "MyField"
+  );
+}
+
+#[test]
+fn test_parse() {
+  let rule: RenameRule = syn::parse_str("\"SCREAMING-KEBAB-CASE\"").unwrap();
+
+  assert_eq!(rule, RenameRule::ScreamingKebabCase);
+
+  let error = syn::parse_str::<RenameRule>("\"camel_case\"").unwrap_err();
+
+  assert!(error
+    .to_string()
+    .contains("Unknown rename rule `camel_case`"));
+}
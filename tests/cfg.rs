@@ -0,0 +1,85 @@
+use quote::ToTokens;
+use syn::{parse_quote, DeriveInput};
+use syn_utils::{filter_attributes_with_cfg, CfgPredicate};
+
+#[test]
+fn test_cfg_attr_metas() {
+  let input: DeriveInput = parse_quote! {
+    #[my(rename = "a")]
+    #[cfg_attr(feature = "serde", my(skip), other(value))]
+    #[cfg_attr(unix, cfg_attr(not(test), my(default)))]
+    struct Example;
+  };
+
+  let metas = filter_attributes_with_cfg(&input.attrs, &["my"]).unwrap();
+
+  assert_eq!(metas.len(), 3);
+  assert!(metas[0].cfg.is_none());
+
+  assert_eq!(metas[1].meta.to_token_stream().to_string(), "skip");
+  assert_eq!(
+    metas[1].cfg_attr().unwrap().to_token_stream().to_string(),
+    "# [cfg (feature = \"serde\")]"
+  );
+
+  assert_eq!(
+    metas[2].cfg.to_token_stream().to_string(),
+    "all (unix , not (test))"
+  );
+}
+
+#[test]
+fn test_unrelated_cfg_attrs() {
+  let input: DeriveInput = parse_quote! {
+    #[cfg_attr(true, allow(dead_code))]
+    #[cfg_attr(some::path(..), other(value))]
+    #[cfg_attr(false, cfg_attr(unix, my(default)))]
+    #[my(skip)]
+    struct Example;
+  };
+
+  let metas = filter_attributes_with_cfg(&input.attrs, &["my"]).unwrap();
+
+  assert_eq!(metas.len(), 2);
+  assert_eq!(
+    metas[0].cfg.to_token_stream().to_string(),
+    "all (false , unix)"
+  );
+  assert!(metas[1].cfg.is_none());
+
+  let input: DeriveInput = parse_quote! {
+    #[cfg_attr(some::path(..), my(skip))]
+    struct Example;
+  };
+
+  assert!(filter_attributes_with_cfg(&input.attrs, &["my"]).is_err());
+}
+
+#[test]
+fn test_evaluate_features() {
+  let predicate: CfgPredicate =
+    syn::parse_str(r#"all(feature = "a", any(feature = "b", feature = "c"), not(feature = "d"))"#)
+      .unwrap();
+
+  assert!(predicate.evaluate_features(&["a", "c"]));
+  assert!(!predicate.evaluate_features(&["a"]));
+  assert!(!predicate.evaluate_features(&["a", "b", "d"]));
+
+  let predicate: CfgPredicate = syn::parse_str("all(unix, feature = \"a\")").unwrap();
+
+  assert!(predicate.evaluate(&mut |key, value| key == "unix" || value == Some("a")));
+
+  let predicate: CfgPredicate = syn::parse_str("any(false, not(true), true)").unwrap();
+
+  assert_eq!(
+    predicate.to_token_stream().to_string(),
+    "any (false , not (true) , true)"
+  );
+  assert!(predicate.evaluate_features(&[]));
+}
+
+#[test]
+fn test_invalid_predicate() {
+  assert!(syn::parse_str::<CfgPredicate>("not(a, b)").is_err());
+  assert!(syn::parse_str::<CfgPredicate>("either(a)").is_err());
+}
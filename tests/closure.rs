@@ -0,0 +1,107 @@
+use quote::{quote, ToTokens};
+use syn::{parse_quote, ExprClosure, Ident, Type};
+use syn_utils::ClosureInfo;
+
+#[test]
+fn test_analysis() {
+  let closure: ExprClosure =
+    parse_quote!(move |v: &str, (a, b), count: Option<u32>| -> u32 { v.len() as u32 + a + b });
+
+  let info = ClosureInfo::from_closure(&closure);
+
+  assert_eq!(info.arity(), 3);
+  assert!(info.is_move);
+  assert!(!info.is_async);
+
+  assert!(info.params[0].type_info.as_ref().unwrap().is_ref());
+  assert!(info.params[1].ty.is_none());
+  assert_eq!(info.params[1].pat.to_token_stream().to_string(), "(a , b)");
+  assert!(info.params[2].type_info.as_ref().unwrap().is_option());
+  assert!(info.output_info.unwrap().is_uint());
+}
+
+#[test]
+fn test_lowering() {
+  let closure: ExprClosure = parse_quote!(|v: &str, limit| v.len() < limit);
+  let info = ClosureInfo::from_closure(&closure);
+
+  let name: Ident = parse_quote!(__validate_name);
+  let usize_type: Type = parse_quote!(usize);
+  let bool_type: Type = parse_quote!(bool);
+
+  let item = info
+    .to_fn(&name, &[usize_type.clone(), usize_type], Some(&bool_type))
+    .unwrap();
+
+  assert_eq!(
+    item.to_token_stream().to_string(),
+    quote! {
+      fn __validate_name(v: &str, limit: usize) -> bool { v.len() < limit }
+    }
+    .to_string()
+  );
+
+  assert!(info.to_fn(&name, &[], None).is_err());
+}
+
+#[test]
+fn test_async_block_body() {
+  let closure: ExprClosure = parse_quote!(async |id: u64| -> String { fetch(id).await });
+  let info = ClosureInfo::from_closure(&closure);
+
+  let item = info.to_fn(&parse_quote!(fetch_name), &[], None).unwrap();
+
+  assert_eq!(
+    item.to_token_stream().to_string(),
+    quote! {
+      async fn fetch_name(id: u64) -> String { fetch(id).await }
+    }
+    .to_string()
+  );
+}
+
+#[test]
+fn test_unsupported_types() {
+  let closure: ExprClosure =
+    parse_quote!(|v: &dyn Display, f: fn(u8) -> u8| -> impl Display { f(1) });
+  let info = ClosureInfo::from_closure(&closure);
+
+  assert!(info.params[0].ty.is_some());
+  assert!(info.params[0].type_info.is_none());
+  assert!(info.params[1].type_info.is_none());
+  assert!(info.output.is_some());
+  assert!(info.output_info.is_none());
+
+  let name: Ident = parse_quote!(__apply);
+  let item = info.to_fn(&name, &[], None).unwrap();
+
+  assert_eq!(
+    item.to_token_stream().to_string(),
+    quote!(
+      fn __apply(v: &dyn Display, f: fn(u8) -> u8) -> impl Display {
+        f(1)
+      }
+    )
+    .to_string()
+  );
+}
+
+#[test]
+fn test_bound_lifetimes_and_attrs() {
+  let closure: ExprClosure =
+    parse_quote!(for<'a> |#[allow(unused)] x: &'a str, y: &str| -> &'a str { x });
+  let info = ClosureInfo::from_closure(&closure);
+
+  assert_eq!(info.lifetimes.len(), 1);
+  assert_eq!(info.params[0].attrs.len(), 1);
+
+  let item = info.to_fn(&parse_quote!(first), &[], None).unwrap();
+
+  assert_eq!(
+    item.to_token_stream().to_string(),
+    quote! {
+      fn first<'a>(#[allow(unused)] x: &'a str, y: &str) -> &'a str { x }
+    }
+    .to_string()
+  );
+}
@@ -0,0 +1,90 @@
+use syn::{parse_quote, Expr, Type};
+use syn_utils::{ConstEvaluator, TypeInfo};
+
+fn eval(expr: Expr) -> syn::Result<i128> {
+  ConstEvaluator::new().eval(&expr)
+}
+
+#[test]
+fn test_arithmetic() {
+  assert_eq!(eval(parse_quote!(1 << 10)).unwrap(), 1024);
+  assert_eq!(eval(parse_quote!(4 * 1024 + 1)).unwrap(), 4097);
+  assert_eq!(eval(parse_quote!(-(2i32.pow(3)))).unwrap(), -8);
+  assert_eq!(eval(parse_quote!((17 % 5) | 0b1000 ^ 1)).unwrap(), 11);
+  assert_eq!(eval(parse_quote!(!0u8)).unwrap(), 255);
+  assert_eq!(eval(parse_quote!((-5i64).abs().max(3))).unwrap(), 5);
+}
+
+#[test]
+fn test_constants_and_casts() {
+  assert_eq!(eval(parse_quote!(i32::MAX)).unwrap(), i32::MAX as i128);
+  assert_eq!(
+    eval(parse_quote!(u64::MAX as i128)).unwrap(),
+    u64::MAX as i128
+  );
+  assert_eq!(eval(parse_quote!(300 as u8)).unwrap(), 44);
+  assert_eq!(eval(parse_quote!(-1i32 as u16)).unwrap(), 65535);
+  assert_eq!(eval(parse_quote!(200u8 as i8)).unwrap(), -56);
+  assert_eq!(eval(parse_quote!(u32::BITS)).unwrap(), 32);
+
+  let evaluator = ConstEvaluator::new()
+    .with_constant("KB", 1024)
+    .with_constant("limits::MAX", 8);
+
+  assert_eq!(
+    evaluator.eval(&parse_quote!(KB * limits::MAX)).unwrap(),
+    8192
+  );
+}
+
+#[test]
+fn test_errors() {
+  assert!(eval(parse_quote!(255u8 + 1)).is_err());
+  assert!(eval(parse_quote!(i32::MAX + 1i32)).is_err());
+  assert!(eval(parse_quote!(1u8 + 1u16)).is_err());
+  assert!(eval(parse_quote!(-1u32)).is_err());
+  assert!(eval(parse_quote!(1 / 0)).is_err());
+  assert!(eval(parse_quote!(1u8 << 8)).is_err());
+  assert!(eval(parse_quote!(UNKNOWN)).is_err());
+  assert!(eval(parse_quote!("abc")).is_err());
+
+  let evaluator = ConstEvaluator::new();
+
+  assert!(evaluator.eval_as::<u8>(&parse_quote!(256)).is_err());
+  assert_eq!(evaluator.eval_as::<u8>(&parse_quote!(255)).unwrap(), 255);
+}
+
+#[test]
+fn test_array_len() {
+  let ty: Type = parse_quote!([u8; 4 * SIZE]);
+  let info = TypeInfo::from_type(&ty).unwrap();
+
+  let len = info
+    .type_
+    .as_array()
+    .unwrap()
+    .eval_len(&ConstEvaluator::new().with_constant("SIZE", 8))
+    .unwrap();
+
+  assert_eq!(len, 32);
+}
+
+#[test]
+fn test_negative_literals_and_shifts() {
+  assert_eq!(eval(parse_quote!(-128i8)).unwrap(), -128);
+  assert_eq!(
+    eval(parse_quote!(-2147483648i32)).unwrap(),
+    i32::MIN as i128
+  );
+  assert!(eval(parse_quote!(-129i8)).is_err());
+  assert!(eval(parse_quote!(-(128i8))).is_err());
+  assert!(eval(parse_quote!(-1u8)).is_err());
+
+  assert_eq!(eval(parse_quote!(3u8 << 7)).unwrap(), 128);
+  assert_eq!(eval(parse_quote!(1i8 << 7)).unwrap(), -128);
+  assert_eq!(eval(parse_quote!(0xFFu8 >> 4)).unwrap(), 15);
+  assert_eq!(eval(parse_quote!(1 << 126)).unwrap(), 1 << 126);
+  assert!(eval(parse_quote!(1 << 127)).is_err());
+  assert!(eval(parse_quote!(3 << 126)).is_err());
+  assert_eq!(eval(parse_quote!(-1 << 127)).unwrap(), i128::MIN);
+}
@@ -0,0 +1,51 @@
+use quote::quote;
+use syn::{parse_quote, DeriveInput};
+use syn_utils::{bail, ControlFlow, MacroResult};
+
+fn expand(
+  input: &DeriveInput,
+  control_flow: &mut ControlFlow,
+) -> syn::Result<proc_macro2::TokenStream> {
+  let ident = &input.ident;
+
+  control_flow.set_dummy(quote! { impl MyTrait for #ident {} });
+
+  if !input.generics.params.is_empty() {
+    bail!(input.generics, "Generics are not supported");
+  }
+
+  Ok(quote! { impl MyTrait for #ident { fn method() {} } })
+}
+
+#[test]
+fn test_success() {
+  let input: DeriveInput = parse_quote! { struct Example; };
+
+  let output = ControlFlow::new().run(|cf| expand(&input, cf));
+
+  assert_eq!(
+    output.to_string(),
+    quote! { impl MyTrait for Example { fn method() {} } }.to_string()
+  );
+}
+
+#[test]
+fn test_dummy_on_error() {
+  let input: DeriveInput = parse_quote! { struct Example<T>(T); };
+
+  let output = ControlFlow::new().run(|cf| expand(&input, cf)).to_string();
+
+  assert!(output.contains("compile_error"));
+  assert!(output.ends_with(&quote! { impl MyTrait for Example {} }.to_string()));
+}
+
+#[test]
+fn test_without_dummy() {
+  let result: syn::Result<proc_macro2::TokenStream> =
+    Err(syn::Error::new(proc_macro2::Span::call_site(), "Failed"));
+
+  let output = result.unwrap_or_dummy(&ControlFlow::new()).to_string();
+
+  assert!(output.starts_with(":: core :: compile_error"));
+  assert!(!output.contains("unimplemented"));
+}
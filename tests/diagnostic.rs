@@ -0,0 +1,50 @@
+use proc_macro2::Span;
+use syn::{parse_quote, spanned::Spanned, Field};
+use syn_utils::{error_with_notes, error_with_span_and_notes, Diagnostic};
+
+#[test]
+fn test_diagnostic_lowering() {
+  let first: Field = parse_quote!(a: i32);
+  let second: Field = parse_quote!(a: String);
+
+  let error = Diagnostic::spanned(&second, "Duplicate field `a`")
+    .span_note(first.span(), "First defined here")
+    .help("Rename one of the fields")
+    .into_error();
+
+  let messages: Vec<String> = error.into_iter().map(|e| e.to_string()).collect();
+
+  assert_eq!(
+    messages,
+    vec![
+      "Duplicate field `a`\n\nhelp: Rename one of the fields".to_string(),
+      "note: First defined here".to_string(),
+    ]
+  );
+}
+
+#[test]
+fn test_macros() {
+  let field: Field = parse_quote!(a: i32);
+  let other = Span::call_site();
+
+  let error = error_with_notes!(
+    field, "Field `{}` conflicts", "a";
+    note(other) = "Conflicting field defined here";
+    note = "Fields must be unique";
+    help(other) = "Consider renaming it to `{}`", "b"
+  );
+
+  let messages: Vec<String> = error.into_iter().map(|e| e.to_string()).collect();
+
+  assert_eq!(messages.len(), 3);
+  assert_eq!(
+    messages[0],
+    "Field `a` conflicts\n\nnote: Fields must be unique"
+  );
+  assert_eq!(messages[2], "help: Consider renaming it to `b`");
+
+  let error = error_with_span_and_notes!(other, "Plain error");
+
+  assert_eq!(error.to_string(), "Plain error");
+}
@@ -0,0 +1,68 @@
+use quote::ToTokens;
+use syn::{parse_quote, DeriveInput};
+use syn_utils::{doc_attributes, DocFragment, Docs};
+
+#[test]
+fn test_doc_text() {
+  let input: DeriveInput = parse_quote! {
+    /// The summary
+    /// continues here.
+    ///
+    ///
+    /// Details:
+    ///   - indented item
+    #[doc(hidden)]
+    struct Example;
+  };
+
+  let docs = Docs::from_attrs(&input.attrs);
+
+  assert_eq!(
+    docs.text(),
+    "The summary\ncontinues here.\n\nDetails:\n  - indented item"
+  );
+  assert_eq!(docs.summary().unwrap(), "The summary continues here.");
+  assert!(!docs.has_include_str());
+}
+
+#[test]
+fn test_include_str() {
+  let input: DeriveInput = parse_quote! {
+    #[doc = include_str!("../README.md")]
+    struct Example;
+  };
+
+  let docs = Docs::from_attrs(&input.attrs);
+
+  assert!(docs.has_include_str());
+  assert_eq!(docs.include_paths().next().unwrap().value(), "../README.md");
+  assert!(docs.summary().is_none());
+
+  let input: DeriveInput = parse_quote! {
+    /// Crate docs:
+    #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))]
+    struct Example;
+  };
+
+  let docs = Docs::from_attrs(&input.attrs);
+
+  assert!(matches!(docs.fragments[1], DocFragment::Expr(_)));
+  assert!(docs.has_include_str());
+  assert!(docs.include_paths().next().is_none());
+  assert_eq!(docs.text(), "Crate docs:");
+}
+
+#[test]
+fn test_round_trip() {
+  let attrs = doc_attributes("First line\n\nSecond paragraph");
+
+  assert_eq!(attrs.len(), 3);
+  assert_eq!(
+    attrs[0].to_token_stream().to_string(),
+    "# [doc = \" First line\"]"
+  );
+
+  let docs = Docs::from_attrs(&attrs);
+
+  assert_eq!(docs.text(), "First line\n\nSecond paragraph");
+}
@@ -0,0 +1,74 @@
+use proc_macro2::Span;
+use syn::{parse_quote, Fields, ItemStruct};
+use syn_utils::{ensure, ensure_call_site, ensure_eq, ensure_with_span};
+
+fn validate(item: &ItemStruct) -> syn::Result<()> {
+  ensure!(
+    matches!(item.fields, Fields::Named(_)),
+    item.fields,
+    "`{}` must have named fields",
+    item.ident
+  );
+  ensure_with_span!(
+    item.generics.params.is_empty(),
+    Span::call_site(),
+    "Generics are not supported"
+  );
+  ensure_eq!(
+    item.fields.len(),
+    2,
+    item.fields,
+    "Expected exactly two fields"
+  );
+  ensure_call_site!(item.attrs.is_empty(), "Attributes are not supported");
+
+  Ok(())
+}
+
+#[test]
+fn test_ensure() {
+  assert!(validate(&parse_quote! { struct A { a: i32, b: i32 } }).is_ok());
+
+  assert_eq!(
+    validate(&parse_quote! { struct A(i32, i32); })
+      .unwrap_err()
+      .to_string(),
+    "`A` must have named fields"
+  );
+
+  assert_eq!(
+    validate(&parse_quote! { struct A<T> { a: T } })
+      .unwrap_err()
+      .to_string(),
+    "Generics are not supported"
+  );
+
+  assert_eq!(
+    validate(&parse_quote! { struct A { a: i32 } })
+      .unwrap_err()
+      .to_string(),
+    "Expected exactly two fields (left: `1`, right: `2`)"
+  );
+
+  assert_eq!(
+    validate(&parse_quote! { #[attr] struct A { a: i32, b: i32 } })
+      .unwrap_err()
+      .to_string(),
+    "Attributes are not supported"
+  );
+}
+
+#[test]
+fn test_ensure_eq_default_message() {
+  let ident: syn::Ident = parse_quote!(value);
+
+  let check = || -> syn::Result<()> {
+    ensure_eq!("a", "b", ident);
+    Ok(())
+  };
+
+  assert_eq!(
+    check().unwrap_err().to_string(),
+    "Values are not equal (left: `\"a\"`, right: `\"b\"`)"
+  );
+}
@@ -0,0 +1,74 @@
+use quote::quote;
+use syn::{parse_quote, ItemFn, LitStr};
+use syn_utils::{error, expand_attribute, expand_derive, expand_function};
+
+#[test]
+fn test_expand_derive() {
+  let output = expand_derive(quote! { struct Example; }, |input, _| {
+    let ident = &input.ident;
+
+    Ok(quote! { impl MyTrait for #ident {} })
+  });
+
+  assert_eq!(
+    output.to_string(),
+    quote! { impl MyTrait for Example {} }.to_string()
+  );
+
+  let output = expand_derive(quote! { not a struct }, |_, _| Ok(quote! {})).to_string();
+
+  assert!(output.contains("compile_error"));
+}
+
+#[test]
+fn test_expand_attribute() {
+  let item = quote! { fn handler() {} };
+
+  let output = expand_attribute(
+    quote! { "/path" },
+    item.clone(),
+    |path: LitStr, item: ItemFn, _| {
+      let ident = &item.sig.ident;
+      let path = path.value();
+
+      Ok(quote! { #item const ROUTE: (&str, &str) = (#path, stringify!(#ident)); })
+    },
+  );
+
+  assert!(output.to_string().contains("\"/path\""));
+
+  // The item is emitted as is, if parsing the args fails
+  let output = expand_attribute(quote! { 1 }, item.clone(), |_: LitStr, item: ItemFn, _| {
+    Ok(item)
+  })
+  .to_string();
+
+  assert!(output.contains("compile_error"));
+  assert!(output.ends_with(&item.to_string()));
+
+  // The dummy can be replaced during the expansion
+  let output = expand_attribute(
+    quote! {},
+    item,
+    |_: proc_macro2::TokenStream, mut item: ItemFn, cf| {
+      item.sig.ident = parse_quote!(renamed);
+      cf.set_dummy(&item);
+
+      Err::<ItemFn, _>(error!(item.sig.ident, "Failed"))
+    },
+  )
+  .to_string();
+
+  assert!(output.contains("fn renamed"));
+}
+
+#[test]
+fn test_expand_function() {
+  let output = expand_function(quote! { "hello" }, |lit: LitStr, _| {
+    let upper = lit.value().to_uppercase();
+
+    Ok(quote! { #upper })
+  });
+
+  assert_eq!(output.to_string(), "\"HELLO\"");
+}
@@ -0,0 +1,49 @@
+use proc_macro2::Span;
+use syn::{parse_quote, DeriveInput, Expr, Fields};
+use syn_utils::{push_error, push_error_call_site, push_error_with_span, Errors, ExprExt};
+
+#[test]
+fn test_collects_every_error() {
+  let input: DeriveInput = parse_quote! {
+    struct Example(i32, i32, String);
+  };
+
+  let syn::Data::Struct(data) = &input.data else {
+    unreachable!()
+  };
+
+  let Fields::Unnamed(fields) = &data.fields else {
+    unreachable!()
+  };
+
+  let mut errors = Errors::new();
+
+  for field in &fields.unnamed {
+    push_error!(errors, field, "Expected a named field");
+  }
+
+  push_error_with_span!(errors, Span::call_site(), "Second error of kind {}", 2);
+  push_error_call_site!(errors, "Third error");
+
+  assert_eq!(errors.len(), 5);
+
+  let error = errors.finish().unwrap_err();
+
+  assert_eq!(error.into_iter().count(), 5);
+}
+
+#[test]
+fn test_push_result() {
+  let mut errors = Errors::new();
+
+  let valid: Expr = parse_quote!(5);
+  let invalid: Expr = parse_quote!("abc");
+
+  assert_eq!(errors.push_result(valid.as_int::<i32>()), Some(5));
+  assert_eq!(errors.push_result(invalid.as_int::<i32>()), None);
+
+  errors.extend(vec![syn::Error::new(Span::call_site(), "Another one")]);
+
+  assert_eq!(errors.len(), 2);
+  assert!(Errors::new().finish_with(1).is_ok());
+}
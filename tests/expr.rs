@@ -0,0 +1,122 @@
+use quote::quote;
+use syn::{parse_quote, Expr};
+use syn_utils::{
+  CallOrClosure, ClosedRangeList, ExprExt, GenericRange, GenericRangeList, PathOrClosure,
+};
+
+#[test]
+fn test_numbers() {
+  let expr: Expr = parse_quote!(-5);
+  assert_eq!(expr.as_int::<i32>().unwrap(), -5);
+  assert!(expr.as_int::<u32>().is_err());
+
+  let expr: Expr = parse_quote!((-(42u8)));
+  assert_eq!(expr.as_int::<i64>().unwrap(), -42);
+
+  let expr: Expr = parse_quote!(-2.5);
+  assert_eq!(expr.as_float::<f64>().unwrap(), -2.5);
+
+  let expr: Expr = parse_quote!(3);
+  assert_eq!(expr.as_float::<f32>().unwrap(), 3.0);
+
+  let expr: Expr = parse_quote!(!5);
+  assert!(expr.as_int::<i32>().is_err());
+}
+
+#[test]
+fn test_other_literals() {
+  let expr: Expr = parse_quote!((true));
+  assert!(expr.as_bool().unwrap());
+
+  let expr: Expr = parse_quote!('x');
+  assert_eq!(expr.as_char().unwrap(), 'x');
+
+  let expr: Expr = parse_quote!(b'x');
+  assert_eq!(expr.as_byte().unwrap(), b'x');
+
+  let expr: Expr = parse_quote!(b"abc");
+  assert_eq!(expr.as_byte_string().unwrap(), b"abc");
+
+  let expr: Expr = parse_quote!(("abc"));
+  assert_eq!(expr.as_lit_str().unwrap().value(), "abc");
+  assert_eq!(expr.as_string().unwrap(), "abc");
+
+  assert!(expr.as_char().is_err());
+}
+
+#[test]
+fn test_negative_ranges() {
+  let list: ClosedRangeList = syn::parse_str("-10..=-1, -20").unwrap();
+
+  let bounds: Vec<(i32, i32)> = list.list.iter().map(|r| (r.start, r.end)).collect();
+
+  assert_eq!(bounds, vec![(-20, -20), (-10, -1)]);
+
+  let list: GenericRangeList = syn::parse_str("-5..").unwrap();
+
+  assert!(matches!(
+    &list.list[0],
+    GenericRange::From { start: -5, .. }
+  ));
+}
+
+#[test]
+fn test_path_or_closure() {
+  let expr: Expr = parse_quote!(my_crate::validate);
+  let path = expr.as_path_or_closure().unwrap();
+
+  assert!(matches!(path, PathOrClosure::Path(_)));
+  assert!(path.check_arity(3).is_ok());
+  assert_eq!(
+    path.invoke([quote!(value)]).to_string(),
+    "my_crate :: validate (value)"
+  );
+
+  let expr: Expr = parse_quote!(|v: &str| v.is_empty());
+  let closure = expr.as_path_or_closure().unwrap();
+
+  let PathOrClosure::Closure(expr_closure) = &closure else {
+    panic!("Expected a closure");
+  };
+
+  assert_eq!(expr_closure.inputs.len(), 1);
+  assert_eq!(
+    closure.invoke([quote!(value)]).to_string(),
+    "(| v : & str | v . is_empty ()) (value)"
+  );
+
+  assert!(closure.check_arity(1).is_ok());
+  assert_eq!(
+    closure.check_arity(2).unwrap_err().to_string(),
+    "Expected a closure with 2 parameters, found 1"
+  );
+}
+
+#[test]
+fn test_call_or_closure() {
+  let expr: Expr = parse_quote!(validate(1, "a"));
+  let call = expr.as_call_or_closure().unwrap();
+
+  let CallOrClosure::Call(expr_call) = &call else {
+    panic!("Expected a call");
+  };
+
+  assert_eq!(expr_call.args.len(), 2);
+  assert_eq!(
+    call.invoke([quote!(value)]).to_string(),
+    "validate (1 , \"a\" , value)"
+  );
+  assert_eq!(
+    call.invoke(Vec::<Expr>::new()).to_string(),
+    "validate (1 , \"a\")"
+  );
+
+  let expr: Expr = parse_quote!(|| true);
+  let closure = expr.as_call_or_closure().unwrap();
+
+  assert_eq!(
+    closure.invoke(Vec::<Expr>::new()).to_string(),
+    "(| | true) ()"
+  );
+  assert!(closure.check_arity(1).is_err());
+}
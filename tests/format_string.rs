@@ -0,0 +1,233 @@
+use quote::{quote, ToTokens};
+use syn::{parse_quote, DeriveInput, ItemStruct, LitStr, Variant};
+use syn_utils::{FormatArg, FormatCount, FormatPiece, FormatString};
+
+fn normalize(tokens: impl ToTokens) -> String {
+  tokens.to_token_stream().to_string().replace(' ', "")
+}
+
+#[test]
+fn test_parsing() {
+  let format: FormatString = parse_quote!("{name} is {0:>5} {{escaped}} {} {:?}");
+
+  let args: Vec<(&FormatArg, Option<String>)> = format
+    .placeholders()
+    .map(|p| (&p.arg, p.spec.as_ref().map(ToString::to_string)))
+    .collect();
+
+  assert_eq!(
+    args,
+    [
+      (&FormatArg::Named("name".to_string()), None),
+      (&FormatArg::Positional(0), Some(">5".to_string())),
+      (&FormatArg::Implicit(0), None),
+      (&FormatArg::Implicit(1), Some("?".to_string())),
+    ]
+  );
+
+  assert!(matches!(&format.pieces[3], FormatPiece::Literal(text) if text == " {escaped} "));
+
+  let ranges: Vec<_> = format.placeholders().map(|p| p.range.clone()).collect();
+  assert_eq!(ranges[0], 0..6);
+
+  for invalid in ["{", "}", "{name", "{a b}", "{r#type}"] {
+    let lit = LitStr::new(invalid, proc_macro2::Span::call_site());
+    assert!(FormatString::from_lit(lit).is_err(), "{invalid}");
+  }
+}
+
+#[test]
+fn test_spec_counts() {
+  let format: FormatString =
+    parse_quote!("{name:>width$} {0:.1$} {:.*} {:05} {:0$} {:-^+#08.3x} {:x}");
+
+  let placeholders: Vec<_> = format.placeholders().collect();
+
+  let spec = placeholders[0].spec.as_ref().unwrap();
+  assert_eq!(spec.flags, ">");
+  assert!(
+    matches!(&spec.width, Some(FormatCount::Arg { arg: FormatArg::Named(name), .. }) if name == "width")
+  );
+  assert!(spec.precision.is_none());
+
+  let spec = placeholders[1].spec.as_ref().unwrap();
+  assert!(spec.width.is_none());
+  assert!(matches!(
+    spec.precision,
+    Some(FormatCount::Arg {
+      arg: FormatArg::Positional(1),
+      ..
+    })
+  ));
+
+  // `.*` takes the implicit argument before the one of the value
+  let spec = placeholders[2].spec.as_ref().unwrap();
+  assert!(matches!(
+    spec.precision,
+    Some(FormatCount::Arg {
+      arg: FormatArg::Implicit(0),
+      ..
+    })
+  ));
+  assert_eq!(placeholders[2].arg, FormatArg::Implicit(1));
+  assert_eq!(placeholders[3].arg, FormatArg::Implicit(2));
+
+  let spec = placeholders[3].spec.as_ref().unwrap();
+  assert_eq!(spec.flags, "0");
+  assert!(matches!(spec.width, Some(FormatCount::Value(5))));
+
+  let spec = placeholders[4].spec.as_ref().unwrap();
+  assert_eq!(spec.flags, "");
+  assert!(matches!(
+    spec.width,
+    Some(FormatCount::Arg {
+      arg: FormatArg::Positional(0),
+      ..
+    })
+  ));
+
+  let spec = placeholders[5].spec.as_ref().unwrap();
+  assert_eq!(spec.flags, "-^+#0");
+  assert!(matches!(spec.width, Some(FormatCount::Value(8))));
+  assert!(matches!(spec.precision, Some(FormatCount::Value(3))));
+  assert_eq!(spec.ty, "x");
+
+  let spec = placeholders[6].spec.as_ref().unwrap();
+  assert!(spec.width.is_none());
+  assert_eq!(spec.ty, "x");
+
+  let specs: Vec<String> = placeholders
+    .iter()
+    .map(|p| p.spec.as_ref().unwrap().to_string())
+    .collect();
+  assert_eq!(
+    specs,
+    [">width$", ".1$", ".*", "05", "0$", "-^+#08.3x", "x"]
+  );
+
+  let lit = LitStr::new("{:.}", proc_macro2::Span::call_site());
+  assert_eq!(
+    FormatString::from_lit(lit).unwrap_err().to_string(),
+    "Expected a precision after `.`"
+  );
+}
+
+#[test]
+fn test_validation() {
+  let item: ItemStruct = parse_quote!(
+    struct Named {
+      name: String,
+      age: u32,
+    }
+  );
+
+  let format: FormatString = parse_quote!("{name} is {age}");
+  assert!(format.validate_fields(&item.fields).is_ok());
+
+  let format: FormatString = parse_quote!("{nme} is {agee} {0}");
+  let errors: Vec<String> = format
+    .validate_fields(&item.fields)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.to_string())
+    .collect();
+
+  assert_eq!(
+    errors,
+    [
+      "Unknown field `nme`",
+      "Unknown field `agee`",
+      "Unknown positional field `0`"
+    ]
+  );
+
+  let variant: Variant = parse_quote!(Tuple(u32, u32));
+  let format: FormatString = parse_quote!("{} {1}");
+  assert!(format.validate_variant(&variant).is_ok());
+
+  let format: FormatString = parse_quote!("{} {} {}");
+  assert!(format.validate_variant(&variant).is_err());
+
+  let format: FormatString = parse_quote!("{name:>width$} {age:.0$}");
+  let errors: Vec<String> = format
+    .validate_fields(&item.fields)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.to_string())
+    .collect();
+
+  assert_eq!(
+    errors,
+    ["Unknown field `width`", "Unknown positional field `0`"]
+  );
+
+  let format: FormatString = parse_quote!("{:.*}");
+  assert!(format.validate_variant(&variant).is_ok());
+
+  let format: FormatString = parse_quote!("{:.*} {}");
+  assert!(format.validate_variant(&variant).is_err());
+
+  let variant: Variant = parse_quote!(Unit);
+  let format: FormatString = parse_quote!("{0}");
+  assert_eq!(
+    format.validate_variant(&variant).unwrap_err().to_string(),
+    "Variant `Unit` has no fields"
+  );
+}
+
+#[test]
+fn test_format_args() {
+  let input: DeriveInput = parse_quote!(
+    struct Tuple(u32, String);
+  );
+
+  let format: FormatString = parse_quote!("{1} = {0:#x} ({0}) {{}}");
+  let syn::Data::Struct(data) = &input.data else {
+    unreachable!()
+  };
+  assert!(format.validate_fields(&data.fields).is_ok());
+
+  let output = format.to_format_args(|member| quote!(self.#member));
+
+  assert_eq!(
+    normalize(output),
+    normalize(quote!(::core::format_args!(
+      "{__1} = {__0:#x} ({__0}) {{}}",
+      __1 = self.1,
+      __0 = self.0
+    )))
+  );
+}
+
+#[test]
+fn test_format_args_with_counts() {
+  let item: ItemStruct = parse_quote!(
+    struct Named {
+      name: String,
+      width: usize,
+    }
+  );
+
+  let format: FormatString = parse_quote!("{name:>width$} {width:<width$}");
+  assert!(format.validate_fields(&item.fields).is_ok());
+
+  assert_eq!(
+    normalize(format.to_format_args(|member| quote!(self.#member))),
+    normalize(quote!(::core::format_args!(
+      "{__name:>__width$} {__width:<__width$}",
+      __name = self.name,
+      __width = self.width
+    )))
+  );
+
+  let format: FormatString = parse_quote!("{0:.1$} {:.*}");
+
+  assert_eq!(
+    normalize(format.to_format_args(|member| quote!(self.#member))),
+    normalize(quote!(::core::format_args!(
+      "{__0:.__1$} {__1:.__0$}",
+      __0 = self.0,
+      __1 = self.1
+    )))
+  );
+}
@@ -0,0 +1,131 @@
+use quote::ToTokens;
+use syn::{parse::Parser, Token};
+use syn_utils::{
+  Braced, Bracketed, IdentList, ListOptions, NumList, Parenthesized, PathList, PunctuatedItems,
+  StringList, TrailingSeparator,
+};
+
+fn parse_with<T, S>(input: &str, options: ListOptions) -> syn::Result<PunctuatedItems<T, S>>
+where
+  T: syn::parse::Parse + ToTokens,
+  S: syn::parse::Parse + ToTokens + Default,
+{
+  (|input: syn::parse::ParseStream| PunctuatedItems::parse_with(input, &options)).parse_str(input)
+}
+
+#[test]
+fn test_default_lists() {
+  let list: IdentList = syn::parse_str("a, b, c,").unwrap();
+  assert_eq!(list.len(), 3);
+  assert_eq!(list.spans().count(), 3);
+  assert_eq!(list.to_token_stream().to_string(), "a , b , c");
+
+  let list: PathList = syn::parse_str("std::fmt::Display").unwrap();
+  assert_eq!(list.len(), 1);
+
+  let list: StringList = syn::parse_str(r#""a", "b""#).unwrap();
+  assert_eq!(list.into_values(), vec!["a", "b"]);
+
+  let list: NumList = syn::parse_str("1, 2, 3").unwrap();
+  assert_eq!(list.into_values(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_separators_and_delimiters() {
+  let list: PathList<Token![|], Bracketed> = syn::parse_str("[a::b | c | d]").unwrap();
+  assert_eq!(list.len(), 3);
+  assert_eq!(list.to_token_stream().to_string(), "[a :: b | c | d]");
+
+  let list: IdentList<Token![+], Parenthesized> = syn::parse_str("(Send + Sync)").unwrap();
+  assert_eq!(list.len(), 2);
+
+  let list: StringList<Token![;], Braced> = syn::parse_str(r#"{ "a"; "b"; }"#).unwrap();
+  assert_eq!(list.values().collect::<Vec<_>>(), ["a", "b"]);
+  assert_eq!(list.to_token_stream().to_string(), r#"{ "a" ; "b" }"#);
+
+  let list: NumList<Token![,], Bracketed> = syn::parse_str("[]").unwrap();
+  assert!(list.is_empty());
+
+  assert!(syn::parse_str::<IdentList<Token![,], Bracketed>>("a, b").is_err());
+  assert!(syn::parse_str::<IdentList<Token![|]>>("a, b").is_err());
+}
+
+#[test]
+fn test_options() {
+  let forbidden = ListOptions::new().with_trailing(TrailingSeparator::Forbidden);
+  assert!(parse_with::<syn::Ident, Token![,]>("a, b", forbidden).is_ok());
+  assert_eq!(
+    parse_with::<syn::Ident, Token![,]>("a, b,", forbidden)
+      .err()
+      .unwrap()
+      .to_string(),
+    "Trailing `,` is not allowed"
+  );
+
+  let required = ListOptions::new().with_trailing(TrailingSeparator::Required);
+  assert!(parse_with::<syn::Ident, Token![;]>("a; b;", required).is_ok());
+  assert!(parse_with::<syn::Ident, Token![;]>("", required).is_ok());
+  assert_eq!(
+    parse_with::<syn::Ident, Token![;]>("a; b", required)
+      .err()
+      .unwrap()
+      .to_string(),
+    "Expected a trailing `;`"
+  );
+
+  let bounded = ListOptions::new().with_min(1).with_max(2);
+  assert!(parse_with::<syn::Ident, Token![,]>("a, b", bounded).is_ok());
+  assert_eq!(
+    parse_with::<syn::Ident, Token![,]>("", bounded)
+      .err()
+      .unwrap()
+      .to_string(),
+    "Expected at least 1 item, found 0"
+  );
+  assert_eq!(
+    parse_with::<syn::Ident, Token![,]>("a, b, c", bounded)
+      .err()
+      .unwrap()
+      .to_string(),
+    "Expected at most 2 items, found 3"
+  );
+}
+
+#[test]
+fn test_constructors() {
+  // `new` creates the default lists without any annotations
+  let list = PunctuatedItems::new(vec![syn::parse_quote!(a::b), syn::parse_quote!(c)]);
+  let _: &PathList = &list;
+  assert_eq!(list.spans().count(), 2);
+  assert_eq!(list.to_token_stream().to_string(), "a :: b , c");
+
+  let list = StringList::new(vec!["a".to_string(), "b".to_string()]);
+  assert_eq!(list.to_token_stream().to_string(), r#""a" , "b""#);
+
+  let list = NumList::new(vec![1, 2]);
+  assert_eq!(list.to_token_stream().to_string(), "1 , 2");
+
+  let list: PathList<Token![|], Bracketed> =
+    vec![syn::parse_quote!(a::b), syn::parse_quote!(c)].into();
+  assert_eq!(list.to_token_stream().to_string(), "[a :: b | c]");
+
+  let list: StringList<Token![;], Braced> = vec!["a".to_string()].into();
+  assert_eq!(list.to_token_stream().to_string(), r#"{ "a" }"#);
+
+  let list: NumList<Token![;], Parenthesized> = vec![1, 2].into();
+  assert_eq!(list.spans().count(), 2);
+  assert_eq!(list.to_token_stream().to_string(), "(1 ; 2)");
+}
+
+#[test]
+fn test_items_keep_their_spans() {
+  let mut list: StringList = syn::parse_str(r#""a", "b""#).unwrap();
+  list
+    .items
+    .push(("c".to_string(), proc_macro2::Span::call_site()));
+  list.items.remove(0);
+
+  assert_eq!(list.values().collect::<Vec<_>>(), ["b", "c"]);
+  assert_eq!(list.spans().count(), list.len());
+  assert_eq!(list.to_token_stream().to_string(), r#""b" , "c""#);
+}
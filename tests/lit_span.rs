@@ -0,0 +1,44 @@
+use syn::{parse_quote, LitStr};
+use syn_utils::LitStrExt;
+
+#[test]
+fn test_plain_and_raw() {
+  let lit: LitStr = parse_quote!("hello {name}");
+  assert_eq!(lit.source_range(6..12), Some(7..13));
+
+  let lit: LitStr = parse_quote!(r#"say "{name}""#);
+  assert_eq!(&lit.value()[5..11], "{name}");
+  assert_eq!(lit.source_range(5..11), Some(8..14));
+
+  let lit: LitStr = parse_quote!("short");
+  assert_eq!(lit.source_range(3..10), None);
+}
+
+#[test]
+fn test_escapes() {
+  let lit: LitStr = parse_quote!("a\n\x41\u{e9}\"{x}");
+  let value = lit.value();
+  let start = value.find('{').unwrap();
+  assert_eq!(&value[start..start + 3], "{x}");
+
+  let source = lit.token().to_string();
+  let range = lit.source_range(start..start + 3).unwrap();
+  assert_eq!(&source[range], "{x}");
+
+  // Starts inside of the two bytes produced by `\u{e9}`
+  let e_start = value.find('é').unwrap();
+  assert_eq!(lit.source_range(e_start + 1..e_start + 2), None);
+}
+
+#[test]
+fn test_line_continuation() {
+  let lit: LitStr = syn::parse_str("\"one \\\n    two\"").unwrap();
+  assert_eq!(lit.value(), "one two");
+
+  let source = lit.token().to_string();
+  let range = lit.source_range(4..7).unwrap();
+  assert_eq!(&source[range], "two");
+
+  let range = lit.source_range(0..4).unwrap();
+  assert_eq!(&source[range], "one ");
+}
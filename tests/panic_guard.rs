@@ -0,0 +1,55 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn_utils::{catch_panics, expand_derive, set_panic_span, MacroResult};
+
+#[test]
+fn test_catch_panic() {
+  let error = catch_panics(|| -> syn::Result<()> {
+    set_panic_span(Span::call_site());
+
+    panic!("Something went {}", "wrong");
+  })
+  .unwrap_err()
+  .to_string();
+
+  assert!(error.starts_with("proc macro panicked: Something went wrong (at tests"));
+  assert!(error.contains("panic_guard.rs"));
+}
+
+#[test]
+fn test_with_macro_result() {
+  let output = catch_panics(|| -> syn::Result<proc_macro2::TokenStream> {
+    let items: Vec<i32> = Vec::new();
+
+    let first = items[0];
+
+    Ok(quote! { #first })
+  })
+  .unwrap_or_unimplemented()
+  .to_string();
+
+  assert!(output.contains("compile_error"));
+  assert!(output.contains("index out of bounds"));
+}
+
+#[test]
+fn test_ok_passes_through() {
+  assert_eq!(catch_panics(|| Ok(1)).unwrap(), 1);
+  assert!(catch_panics(|| -> syn::Result<()> {
+    Err(syn::Error::new(Span::call_site(), "Regular error"))
+  })
+  .unwrap_err()
+  .to_string()
+  .eq("Regular error"));
+}
+
+#[test]
+fn test_entry_guard() {
+  let output = expand_derive(
+    quote! { struct Example; },
+    |_, _| -> syn::Result<proc_macro2::TokenStream> { panic!("Unexpected input") },
+  )
+  .to_string();
+
+  assert!(output.contains("proc macro panicked: Unexpected input"));
+}
@@ -0,0 +1,286 @@
+use quote::{quote, ToTokens};
+use syn_utils::{ClosedRangeList, GenericRange, GenericRangeList};
+
+fn bounds<T: Copy>(list: &ClosedRangeList<T>) -> Vec<(T, T)> {
+  list.list.iter().map(|r| (r.start, r.end)).collect()
+}
+
+fn parse_err<T: syn::parse::Parse>(input: &str) -> String {
+  match syn::parse_str::<T>(input) {
+    Ok(_) => panic!("Expected `{input}` to fail"),
+    Err(e) => e.to_string(),
+  }
+}
+
+#[test]
+fn test_integer_types() {
+  let list: ClosedRangeList<u64> =
+    syn::parse_str("5_000_000_000..6_000_000_000, 19000..=19999").unwrap();
+
+  assert_eq!(
+    bounds(&list),
+    vec![(19000, 19999), (5_000_000_000, 5_999_999_999)]
+  );
+
+  let list: ClosedRangeList<u8> = syn::parse_str("250..=255, 0").unwrap();
+  assert_eq!(bounds(&list), vec![(0, 0), (250, 255)]);
+
+  let list: ClosedRangeList<i8> = syn::parse_str("-128..-100").unwrap();
+  assert_eq!(bounds(&list), vec![(-128, -101)]);
+}
+
+#[test]
+fn test_inclusive_end_at_max() {
+  let list: ClosedRangeList = syn::parse_str("10..=2147483647").unwrap();
+
+  assert_eq!(bounds(&list), vec![(10, i32::MAX)]);
+  assert_eq!(list.list[0].as_exclusive(), None);
+
+  let list: GenericRangeList<u8> = syn::parse_str("200..=255, 10..").unwrap();
+  assert!(matches!(
+    list.list[0],
+    GenericRange::Closed(range) if range.end == u8::MAX
+  ));
+  assert!(matches!(list.list[1], GenericRange::From { start: 10, .. }));
+}
+
+#[test]
+fn test_errors() {
+  assert_eq!(
+    parse_err::<ClosedRangeList<u16>>("1..5u8"),
+    "Expected a `u16` value, found a `u8` literal"
+  );
+  assert_eq!(
+    parse_err::<ClosedRangeList<u8>>("1..=256"),
+    "Value is out of range for `u8`"
+  );
+  assert_eq!(
+    parse_err::<ClosedRangeList<u32>>("-1"),
+    "Value is out of range for `u32`"
+  );
+  assert_eq!(parse_err::<ClosedRangeList>("5..5"), "This range is empty");
+  assert_eq!(parse_err::<ClosedRangeList>("5..=1"), "This range is empty");
+  for input in ["1..", "..5", "..", "foo"] {
+    assert_eq!(
+      parse_err::<ClosedRangeList>(input),
+      "Expected a closed range (`1..5` or `1..=5`) or a single value, with literals or `MIN`/`MAX` constants (like `u8::MAX`) as bounds"
+    );
+  }
+
+  assert!(syn::parse_str::<ClosedRangeList<u16>>("1..5u16, 7u16").is_ok());
+}
+
+fn list(input: &str) -> ClosedRangeList<u32> {
+  syn::parse_str(input).unwrap()
+}
+
+#[test]
+fn test_normalize() {
+  let mut ranges = list("10..=20, 1..=4, 3, 5..8, 21, 30");
+  ranges.normalize();
+
+  assert_eq!(bounds(&ranges), vec![(1, 7), (10, 21), (30, 30)]);
+
+  let ranges: ClosedRangeList<u8> = syn::parse_str("250..=255, 0..=255").unwrap();
+  assert_eq!(bounds(&ranges.normalized()), vec![(0, 255)]);
+}
+
+#[test]
+fn test_set_operations() {
+  let a = list("1..=10, 20..=30");
+  let b = list("5..=25");
+
+  assert!(a.contains(7));
+  assert!(!a.contains(15));
+  assert!(a.overlaps_with(&b));
+  assert!(!a.overlaps_with(&list("11..20")));
+
+  assert_eq!(bounds(&a.intersection(&b)), vec![(5, 10), (20, 25)]);
+  assert_eq!(bounds(&a.union(&b)), vec![(1, 30)]);
+  assert_eq!(bounds(&a.difference(&b)), vec![(1, 4), (26, 30)]);
+  assert_eq!(
+    bounds(&b.difference(&list("5, 7..=8, 25"))),
+    vec![(6, 6), (9, 24)]
+  );
+
+  assert_eq!(
+    bounds(&a.complement_within(0..=40)),
+    vec![(0, 0), (11, 19), (31, 40)]
+  );
+  assert_eq!(
+    bounds(&list("0..=4294967295").complement_within(0..=u32::MAX)),
+    vec![]
+  );
+  assert_eq!(
+    bounds(&list("0..=5").complement_within(0..=u32::MAX)),
+    vec![(6, u32::MAX)]
+  );
+}
+
+#[test]
+fn test_overlap_errors() {
+  assert!(list("1..=4, 5..=8, 10").check_overlaps().is_ok());
+
+  let messages: Vec<String> = list("1..=10, 5..=8, 10, 20")
+    .check_overlaps()
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.to_string())
+    .collect();
+
+  assert_eq!(
+    messages,
+    vec![
+      "Range `5..=8` overlaps with `1..=10`",
+      "note: `1..=10` is defined here",
+      "Range `10` overlaps with `1..=10`",
+      "note: `1..=10` is defined here",
+    ]
+  );
+
+  let error = list("1..5")
+    .check_overlaps_with(&list("4..=6"))
+    .unwrap_err();
+  assert_eq!(error.to_string(), "Range `1..=4` overlaps with `4..=6`");
+
+  assert!(list("1..5")
+    .check_excludes(5, proc_macro2::Span::call_site())
+    .is_ok());
+  assert_eq!(
+    list("1..5")
+      .check_excludes(3, proc_macro2::Span::call_site())
+      .unwrap_err()
+      .to_string(),
+    "Value `3` is within the range `1..=4`"
+  );
+}
+
+fn normalize_tokens(tokens: impl ToTokens) -> String {
+  tokens.to_token_stream().to_string().replace(' ', "")
+}
+
+#[test]
+fn test_closed_tokens() {
+  let ranges = list("10..=20, 1..=4, 3, 5..8, 30");
+
+  assert_eq!(normalize_tokens(&ranges), "1..=7|10..=20|30");
+  assert_eq!(
+    normalize_tokens(ranges.to_contains_expr(quote!(self.tag))),
+    "::core::matches!(self.tag,1..=7|10..=20|30)"
+  );
+  assert_eq!(
+    normalize_tokens(ranges.to_array()),
+    "[1..=7,10..=20,30..=30]"
+  );
+  assert_eq!(
+    normalize_tokens(ranges.to_const(&syn::parse_quote!(RESERVED))),
+    "constRESERVED:[::core::ops::RangeInclusive<u32>;3usize]=[1..=7,10..=20,30..=30];"
+  );
+
+  let empty = list("");
+  assert_eq!(normalize_tokens(empty.to_contains_expr(quote!(x))), "false");
+}
+
+#[test]
+fn test_generic_tokens() {
+  let ranges: GenericRangeList<u32> = syn::parse_str("100.., 1..=4, 5, 90..=120, 10").unwrap();
+
+  assert_eq!(normalize_tokens(&ranges), "1..=5|10|90..");
+  assert!(ranges.contains(1000));
+  assert!(!ranges.contains(50));
+
+  assert_eq!(
+    normalize_tokens(ranges.to_array()),
+    normalize_tokens(quote!([
+      (
+        ::core::ops::Bound::Included(1),
+        ::core::ops::Bound::Included(5)
+      ),
+      (
+        ::core::ops::Bound::Included(10),
+        ::core::ops::Bound::Included(10)
+      ),
+      (
+        ::core::ops::Bound::Included(90),
+        ::core::ops::Bound::Unbounded
+      )
+    ]))
+  );
+
+  let ranges: GenericRangeList<u8> = syn::parse_str("0..=255, 3..").unwrap();
+  assert_eq!(normalize_tokens(&ranges), "0..");
+}
+
+#[test]
+fn test_generic_syntax() {
+  let ranges: GenericRangeList<u8> = syn::parse_str("..5, ..=10, 20..u8::MAX, 250..").unwrap();
+
+  assert!(matches!(ranges.list[0], GenericRange::To { end: 4, .. }));
+  assert!(matches!(ranges.list[1], GenericRange::To { end: 10, .. }));
+  assert!(matches!(
+    ranges.list[2],
+    GenericRange::Closed(range) if range.start == 20 && range.end == 254
+  ));
+  assert!(matches!(
+    ranges.list[3],
+    GenericRange::From { start: 250, .. }
+  ));
+
+  assert_eq!(normalize_tokens(&ranges), "..=10|20..");
+  assert!(ranges.contains(0));
+  assert!(!ranges.contains(15));
+
+  let ranges: GenericRangeList<i64> = syn::parse_str("i64::MIN..=-1, 0, ..").unwrap();
+  assert!(matches!(ranges.list[2], GenericRange::Full { .. }));
+  assert_eq!(normalize_tokens(&ranges), "_");
+  assert_eq!(
+    normalize_tokens(ranges.to_array()),
+    "[(::core::ops::Bound::Unbounded,::core::ops::Bound::Unbounded)]"
+  );
+
+  let ranges: ClosedRangeList<u16> = syn::parse_str("u16::MIN..=10, 65000..=u16::MAX").unwrap();
+  assert_eq!(bounds(&ranges), vec![(0, 10), (65000, u16::MAX)]);
+}
+
+#[test]
+fn test_char_ranges() {
+  let ranges: GenericRangeList<char> = syn::parse_str("'a'..='z', 'A'..'G', '_'").unwrap();
+
+  assert!(ranges.contains('m'));
+  assert!(ranges.contains('F'));
+  assert!(!ranges.contains('G'));
+  assert_eq!(normalize_tokens(&ranges), "'A'..='F'|'_'|'a'..='z'");
+
+  let ranges: GenericRangeList<char> = syn::parse_str("'a'..='z', '0'..").unwrap();
+  assert!(ranges.contains('\u{10FFFF}'));
+  assert_eq!(normalize_tokens(&ranges), "'0'..");
+
+  let ranges: ClosedRangeList<char> =
+    syn::parse_str("'a'..='c', 'd', '\u{D7FF}', '\u{E000}'").unwrap();
+  assert_eq!(
+    bounds(&ranges.normalized()),
+    vec![('a', 'd'), ('\u{D7FF}', '\u{E000}')]
+  );
+  assert_eq!(ranges.list[0].to_string(), "'a'..='c'");
+}
+
+#[test]
+fn test_rejected_syntax() {
+  assert_eq!(
+    parse_err::<GenericRangeList<u8>>("foo"),
+    "Expected a range (`1..5`, `1..=5`, `1..`, `..5`, `..=5` or `..`) or a single value, with literals or `MIN`/`MAX` constants (like `u8::MAX`) as bounds"
+  );
+  assert_eq!(
+    parse_err::<GenericRangeList<u8>>("..u16::MAX"),
+    "Expected `u8::MIN` or `u8::MAX`, found `u16::MAX`"
+  );
+  assert_eq!(
+    parse_err::<GenericRangeList<u8>>("u8::BITS"),
+    "Expected `u8::MIN` or `u8::MAX`, found `u8::BITS`"
+  );
+  assert_eq!(
+    parse_err::<GenericRangeList<u8>>("..0"),
+    "This range is empty"
+  );
+  assert!(syn::parse_str::<GenericRangeList<u8>>("'a'..").is_err());
+}
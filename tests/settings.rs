@@ -0,0 +1,77 @@
+use syn::{parse_quote, Meta};
+use syn_utils::{Cascade, ExprExt, SettingLayers, SettingLevel};
+
+fn rename_all(meta: &Meta) -> syn::Result<String> {
+  meta.require_name_value()?.value.as_string()
+}
+
+#[test]
+fn test_override() {
+  let container = SettingLayers::new(vec![
+    parse_quote!(rename_all = "camelCase"),
+    parse_quote!(skip_defaults),
+  ]);
+
+  let variant = container.with_variant(vec![parse_quote!(rename_all = "snake_case")]);
+  let field = variant.with_field(vec![parse_quote!(skip_defaults = false)]);
+
+  let rename = field
+    .resolve("rename_all", Cascade::Override, rename_all)
+    .unwrap()
+    .unwrap();
+
+  assert_eq!(rename.value, "snake_case");
+  assert_eq!(rename.level, SettingLevel::Variant);
+
+  let skip = field
+    .resolve_flag("skip_defaults", Cascade::Override)
+    .unwrap()
+    .unwrap();
+
+  assert!(!skip.value);
+  assert_eq!(skip.level, SettingLevel::Field);
+
+  let skip = container
+    .resolve_flag("skip_defaults", Cascade::Override)
+    .unwrap()
+    .unwrap();
+
+  assert!(skip.value);
+  assert_eq!(skip.level, SettingLevel::Container);
+
+  assert!(field
+    .resolve_flag("missing", Cascade::Override)
+    .unwrap()
+    .is_none());
+}
+
+#[test]
+fn test_strict_conflict() {
+  let container = SettingLayers::new(vec![parse_quote!(crate_path = "a")]);
+
+  let agreeing = container.with_field(vec![parse_quote!(crate_path = "a")]);
+
+  assert_eq!(
+    agreeing
+      .resolve("crate_path", Cascade::Strict, rename_all)
+      .unwrap()
+      .unwrap()
+      .level,
+    SettingLevel::Field
+  );
+
+  let conflicting = container.with_field(vec![parse_quote!(crate_path = "b")]);
+
+  let error = conflicting
+    .resolve("crate_path", Cascade::Strict, rename_all)
+    .unwrap_err();
+
+  assert_eq!(error.into_iter().count(), 2);
+}
+
+#[test]
+fn test_duplicate() {
+  let layers = SettingLayers::new(vec![parse_quote!(skip), parse_quote!(skip)]);
+
+  assert!(layers.resolve_flag("skip", Cascade::Override).is_err());
+}
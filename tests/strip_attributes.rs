@@ -0,0 +1,112 @@
+use quote::ToTokens;
+use syn::{parse_quote, Item, Member};
+use syn_utils::{strip_attributes, AttrLocation};
+
+#[test]
+fn test_strip_struct() {
+  let mut item: Item = parse_quote! {
+    #[derive(Debug)]
+    #[my(rename_all = "camelCase")]
+    struct Example {
+      #[my(skip)]
+      #[serde(default)]
+      first: i32,
+      second: String,
+    }
+  };
+
+  let stripped = strip_attributes(&mut item, &["my"]).unwrap();
+
+  assert_eq!(stripped.len(), 2);
+  assert_eq!(stripped[0].location, AttrLocation::Item);
+  assert_eq!(
+    stripped[1].location,
+    AttrLocation::Field {
+      variant: None,
+      member: Member::Named(parse_quote!(first))
+    }
+  );
+
+  let metas = stripped[1].metas().unwrap();
+  assert_eq!(metas[0].to_token_stream().to_string(), "skip");
+
+  let output = item.to_token_stream().to_string();
+  assert!(!output.contains("my"));
+  assert!(output.contains("serde"));
+  assert!(output.contains("derive"));
+}
+
+#[test]
+fn test_strip_enum() {
+  let mut item: Item = parse_quote! {
+    enum Example {
+      #[my(rename = "a")]
+      A(#[my(skip)] i32, i32),
+      B,
+    }
+  };
+
+  let stripped = strip_attributes(&mut item, &["my"]).unwrap();
+
+  assert_eq!(stripped[0].location, AttrLocation::Variant(parse_quote!(A)));
+  assert_eq!(
+    stripped[1].location,
+    AttrLocation::Field {
+      variant: Some(parse_quote!(A)),
+      member: Member::Unnamed(0.into())
+    }
+  );
+  assert!(!item.to_token_stream().to_string().contains("my"));
+}
+
+#[test]
+fn test_strip_impl_and_trait() {
+  let mut item: Item = parse_quote! {
+    impl Example {
+      #[my(route = "/")]
+      fn handler(&self, #[my(query)] query: String) {}
+
+      #[my]
+      const VALUE: i32 = 1;
+    }
+  };
+
+  let stripped = strip_attributes(&mut item, &["my"]).unwrap();
+
+  assert_eq!(
+    stripped
+      .iter()
+      .map(|s| s.location.clone())
+      .collect::<Vec<_>>(),
+    vec![
+      AttrLocation::AssocItem(parse_quote!(handler)),
+      AttrLocation::FnParam {
+        method: Some(parse_quote!(handler)),
+        index: 1
+      },
+      AttrLocation::AssocItem(parse_quote!(VALUE)),
+    ]
+  );
+
+  let mut item: Item = parse_quote! {
+    trait Example {
+      #[my(skip)]
+      fn method(#[my] value: i32);
+    }
+  };
+
+  let stripped = strip_attributes(&mut item, &["my"]).unwrap();
+
+  assert_eq!(stripped.len(), 2);
+  assert!(!item.to_token_stream().to_string().contains("my"));
+}
+
+#[test]
+fn test_unsupported_item() {
+  let mut item: Item = parse_quote! {
+    #[my]
+    mod example {}
+  };
+
+  assert!(strip_attributes(&mut item, &["my"]).is_err());
+}
@@ -0,0 +1,56 @@
+use quote::quote;
+use syn::{parse_quote, DeriveInput, Path, Signature};
+use syn_utils::{bail, expand_derive_or_stub, stub_trait_impl};
+
+fn methods() -> Vec<Signature> {
+  vec![
+    parse_quote!(fn name(&self) -> String),
+    parse_quote!(fn from_value(value: i32) -> Self),
+  ]
+}
+
+#[test]
+fn test_stub_impl() {
+  let input: DeriveInput = parse_quote! {
+    struct Example<'a, T: Clone> where T: Default {
+      inner: &'a T,
+    }
+  };
+
+  let trait_path: Path = parse_quote!(my_crate::Named);
+
+  let output = stub_trait_impl(&input, &trait_path, &methods());
+
+  let expected = quote! {
+    #[automatically_derived]
+    #[allow(unused_variables, clippy::all)]
+    impl<'a, T: Clone> my_crate::Named for Example<'a, T> where T: Default {
+      fn name(&self) -> String {
+        ::core::unimplemented!()
+      }
+      fn from_value(value: i32) -> Self {
+        ::core::unimplemented!()
+      }
+    }
+  };
+
+  assert_eq!(output.to_string(), expected.to_string());
+}
+
+#[test]
+fn test_expand_derive_or_stub() {
+  let input: DeriveInput = parse_quote! { enum Example {} };
+  let trait_path: Path = parse_quote!(Named);
+
+  let output = expand_derive_or_stub(&input, &trait_path, &methods(), |input| {
+    if let syn::Data::Enum(_) = &input.data {
+      bail!(input.ident, "Enums are not supported");
+    }
+
+    Ok(quote! {})
+  })
+  .to_string();
+
+  assert!(output.contains("compile_error"));
+  assert!(output.contains("impl Named for Example"));
+}
@@ -0,0 +1,264 @@
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Block, Expr, Item, Type};
+use syn_utils::Substitutions;
+
+fn normalize(tokens: impl ToTokens) -> String {
+  tokens.to_token_stream().to_string().replace(' ', "")
+}
+
+#[test]
+fn test_expr() {
+  let subs = Substitutions::new()
+    .with("self", quote!(value))
+    .with("Self", quote!(MyType<T>))
+    .with("input", quote!(a + b));
+
+  let mut expr: Expr = parse_quote!(self.len() > Self::MAX && input * 2 > 0);
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(value.len() > <MyType<T>>::MAX && (a + b) * 2 > 0))
+  );
+
+  let mut expr: Expr = parse_quote!(Self { input, other: self });
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(MyType::<T> {
+      input: (a + b),
+      other: value
+    }))
+  );
+}
+
+#[test]
+fn test_shadowing() {
+  let subs = Substitutions::new().with("x", quote!(__binding));
+
+  let mut block: Block = parse_quote!({
+    let y = x + 1;
+    let f = |x: i32| x * 2;
+    let x = x.clone();
+    match y {
+      Some(x) => x,
+      None => 0,
+    };
+    format!("{}", x)
+  });
+
+  subs.apply_to_block(&mut block);
+
+  let expected: Block = parse_quote!({
+    let y = __binding + 1;
+    let f = |x: i32| x * 2;
+    let x = __binding.clone();
+    match y {
+      Some(x) => x,
+      None => 0,
+    };
+    format!("{}", x)
+  });
+
+  assert_eq!(normalize(&block), normalize(&expected));
+}
+
+#[test]
+fn test_conditional_shadowing() {
+  let subs = Substitutions::new().with("value", quote!(__v));
+
+  let mut expr: Expr = parse_quote!(if let Some(value) = opt
+    && value > 0
+  {
+    value
+  } else {
+    value
+  });
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(if let Some(value) = opt
+      && value > 0
+    {
+      value
+    } else {
+      __v
+    }))
+  );
+
+  let mut expr: Expr = parse_quote!(while let Some(value) = value.next() {
+    value;
+  });
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(while let Some(value) = __v.next() {
+      value;
+    }))
+  );
+
+  let mut expr: Expr = parse_quote!(match opt {
+    Some(x) if let Some(value) = x.get() => value,
+    _ => value,
+  });
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(match opt {
+      Some(x) if let Some(value) = x.get() => value,
+      _ => __v,
+    }))
+  );
+}
+
+#[test]
+fn test_fn_params_shadowing() {
+  let subs = Substitutions::new()
+    .with("value", quote!(__v))
+    .with("self", quote!(__self));
+
+  let mut item: Item = parse_quote!(
+    fn f(value: u8) -> u8 {
+      value + 1
+    }
+  );
+  subs.apply_to_item(&mut item);
+
+  assert_eq!(
+    normalize(&item),
+    normalize(quote!(
+      fn f(value: u8) -> u8 {
+        value + 1
+      }
+    ))
+  );
+
+  let mut item: Item = parse_quote!(
+    impl Foo {
+      fn get(&self) -> u8 {
+        self.0 + value
+      }
+    }
+  );
+  subs.apply_to_item(&mut item);
+
+  assert_eq!(
+    normalize(&item),
+    normalize(quote!(
+      impl Foo {
+        fn get(&self) -> u8 {
+          self.0 + __v
+        }
+      }
+    ))
+  );
+
+  let mut item: Item = parse_quote!(
+    trait Foo {
+      fn get(&self, (value, _): (u8, u8)) -> u8 {
+        self.len() + value
+      }
+    }
+  );
+  subs.apply_to_item(&mut item);
+
+  assert_eq!(
+    normalize(&item),
+    normalize(quote!(
+      trait Foo {
+        fn get(&self, (value, _): (u8, u8)) -> u8 {
+          self.len() + value
+        }
+      }
+    ))
+  );
+}
+
+#[test]
+fn test_type_and_item() {
+  let subs = Substitutions::new().with("Self", quote!(Wrapper));
+
+  let mut ty: Type = parse_quote!(Vec<Self>);
+  subs.apply_to_type(&mut ty);
+  assert_eq!(normalize(&ty), normalize(quote!(Vec<Wrapper>)));
+
+  let mut item: Item = parse_quote! {
+    fn build() -> Self::Output {
+      println!("{}", Self::NAME);
+      Self::new()
+    }
+  };
+  subs.apply_to_item(&mut item);
+
+  assert_eq!(
+    normalize(&item),
+    normalize(quote! {
+      fn build() -> <Wrapper>::Output {
+        println!("{}", Wrapper::NAME);
+        <Wrapper>::new()
+      }
+    })
+  );
+}
+
+#[test]
+fn test_raw_tokens() {
+  let subs = Substitutions::new().with("PLACEHOLDER", quote!(self.inner));
+
+  let output = subs.apply_to_tokens(quote!(some + [weird PLACEHOLDER] => fragment));
+
+  assert_eq!(
+    output.to_string(),
+    quote!(some + [weird self.inner] => fragment).to_string()
+  );
+}
+
+#[test]
+fn test_generic_self() {
+  let subs = Substitutions::new().with("Self", quote!(MyType<T>));
+
+  let mut expr: Expr = parse_quote!(Self(1));
+  subs.apply_to_expr(&mut expr);
+  assert_eq!(normalize(&expr), normalize(quote!(MyType::<T>(1))));
+  assert!(syn::parse2::<Expr>(expr.to_token_stream()).is_ok());
+
+  let mut expr: Expr = parse_quote!(Self);
+  subs.apply_to_expr(&mut expr);
+  assert_eq!(normalize(&expr), normalize(quote!(MyType::<T>)));
+
+  let mut expr: Expr = parse_quote!(match v {
+    Self::A => 1,
+    Self::B(x) => x,
+    Self { a, .. } => a,
+    Self => 0,
+  });
+  subs.apply_to_expr(&mut expr);
+
+  assert_eq!(
+    normalize(&expr),
+    normalize(quote!(match v {
+      MyType::<T>::A => 1,
+      MyType::<T>::B(x) => x,
+      MyType::<T> { a, .. } => a,
+      MyType::<T> => 0,
+    }))
+  );
+
+  let mut block: Block = parse_quote!({
+    let Self(inner) = value;
+    if let Self::A = other {}
+  });
+  subs.apply_to_block(&mut block);
+
+  assert_eq!(
+    normalize(&block),
+    normalize(quote!({
+      let MyType::<T>(inner) = value;
+      if let MyType::<T>::A = other {}
+    }))
+  );
+}
@@ -0,0 +1,26 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::{parse_quote, Meta};
+use syn_utils::{push_warning, push_warning_with_span, Warnings};
+
+#[test]
+fn test_warnings() {
+  let meta: Meta = parse_quote!(old_key = 1);
+
+  let mut warnings = Warnings::new();
+
+  push_warning!(warnings, meta, "`{}` is deprecated", "old_key");
+  push_warning_with_span!(warnings, Span::call_site(), "Suspicious configuration");
+
+  assert_eq!(warnings.len(), 2);
+  assert_eq!(warnings.list[0].message, "`old_key` is deprecated");
+
+  let output = warnings.to_token_stream().to_string();
+
+  assert_eq!(output.matches("deprecated (note =").count(), 2);
+  assert!(output.contains("\"`old_key` is deprecated\""));
+
+  // The output must be valid items
+  let file: syn::File = syn::parse2(warnings.to_token_stream()).unwrap();
+  assert_eq!(file.items.len(), 2);
+}